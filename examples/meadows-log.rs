@@ -3,11 +3,10 @@
 //! An example program that shows how to set up `tracing` using [`meadows::tracing::config::init`].
 
 use std::io::prelude::*;
-use std::process;
 
+use meadows::bail_process;
 use meadows::io;
 use meadows::process::ExecType;
-use meadows::process_error;
 use meadows::tracing::config;
 use meadows::tracing::config::Config;
 use tracing::info;
@@ -25,7 +24,7 @@ fn run() -> anyhow::Result<()> {
 
 // `main` ---------------------------------------------------------------------------------------------------
 
-fn main() -> anyhow::Result<()> {
+fn main() {
   // Init logging
 
   config::init(&Config::new(ExecType::Example));
@@ -33,10 +32,8 @@ fn main() -> anyhow::Result<()> {
   // Run
 
   if let Err(err) = run() {
-    process_error!(io::stdout(), "{err:#}")?;
-    process::exit(1);
+    bail_process!(io::stdout(), 1, "{err:#}");
   }
-  Ok(())
 }
 
 // EOF
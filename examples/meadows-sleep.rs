@@ -1,13 +1,16 @@
 // meadows-sleep.rs
 
-//! An example program that sleeps for a given amount of seconds.
+//! An example program that sleeps for a given amount of seconds. Pressing Ctrl-C stops sleeping early.
 
 use std::io;
 use std::io::prelude::*;
+use std::sync::atomic::Ordering;
 use std::thread;
 use std::time::Duration;
+use std::time::Instant;
 
 use clap::Parser;
+use meadows::process;
 
 #[derive(Parser)]
 #[command(about = "Sleeps for a given number of seconds", version)]
@@ -25,7 +28,15 @@ fn main() -> anyhow::Result<()> {
     let mut stdout = io::stdout();
     let noun = if n == 1 { "second" } else { "seconds" };
     writeln!(stdout, "Sleeping {n} {noun} ...")?;
-    thread::sleep(Duration::from_secs(n));
+
+    let shutdown = process::install_shutdown_handler()?;
+    let deadline = Instant::now() + Duration::from_secs(n);
+    while !shutdown.load(Ordering::Relaxed) && Instant::now() < deadline {
+      thread::sleep(Duration::from_millis(100));
+    }
+    if shutdown.load(Ordering::Relaxed) {
+      writeln!(stdout, "Interrupted")?;
+    }
   }
 
   Ok(())
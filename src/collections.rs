@@ -2,8 +2,72 @@
 
 //! Collections and collection-related utilities.
 
+pub mod oset;
 pub mod uvec;
 
+use std::hash::Hash;
+
+pub use oset::Oset;
+pub use uvec::BoundedUvec;
+pub use uvec::PushResult;
 pub use uvec::Uvec;
 
+// Functions ------------------------------------------------------------------------------------------------
+
+/// Merges a primary and a fallback sequence into a [`Uvec`].
+///
+/// All values of `primary` are pushed first, followed by all values of `fallback`. Hence, values from
+/// `primary` take precedence: a value from `fallback` is dropped if a value with the same key has been
+/// pushed before. Within each sequence, the first value with a given key wins as well. Values for which
+/// `key` returns [`None`] are dropped, see [`Uvec::with_key`].
+///
+/// # Examples
+///
+/// ```
+/// use meadows::collections;
+///
+/// let key = |&(name, _): &(char, i32)| Some(name);
+/// let primary = [('c', 1), ('w', 2)];
+/// let fallback = [('w', 80), ('h', 24), ('c', 0)];
+///
+/// let merged = collections::merge_unique(&key, primary, fallback);
+/// assert_eq!(merged.as_slice(), [('c', 1), ('w', 2), ('h', 24)]);
+/// ```
+pub fn merge_unique<V, K, F, I1, I2>(key: &F, primary: I1, fallback: I2) -> Uvec<'_, K, V>
+where
+  K: Eq + Hash,
+  F: Fn(&V) -> Option<K>,
+  I1: IntoIterator<Item = V>,
+  I2: IntoIterator<Item = V>, {
+  let mut ret = Uvec::with_key(key);
+  ret.extend(primary);
+  ret.extend(fallback);
+  ret
+}
+
+// Tests ====================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // Functions ----------------------------------------------------------------------------------------------
+
+  #[test]
+  fn test_merge_unique() {
+    let key = |val: &String| Some(val.to_lowercase());
+    let primary = ["b", "A", "c", "a"].map(String::from);
+    let fallback = ["a", "D", "C", "e", "d"].map(String::from);
+
+    let merged = merge_unique(&key, primary, fallback);
+    assert_eq!(merged.as_slice(), ["b", "A", "c", "D", "e"]);
+
+    let merged = merge_unique(&key, [], ["x", "X"].map(String::from));
+    assert_eq!(merged.as_slice(), ["x"]);
+
+    let none = |_: &i32| None::<i32>;
+    assert!(merge_unique(&none, [1, 2], [3]).is_empty());
+  }
+}
+
 // EOF
@@ -0,0 +1,282 @@
+// oset.rs
+
+//! An [`Oset`] is a set that retains the insertion order of its elements.
+//!
+//! Unlike a [`Uvec`], whose API is modeled after [`Vec`], an [`Oset`] offers the API of a set. The elements
+//! themselves are used as keys, so there is no separate key type. Internally, an [`Oset`] is backed by a
+//! [`Uvec`] whose key-generating function clones the elements. Since this function does not capture
+//! anything, it lives for `'static`, so unlike a [`Uvec`], an [`Oset`] has no lifetime parameter. In turn,
+//! the elements must not contain non-`'static` references.
+//!
+//! ```
+//! use meadows::collections::Oset;
+//!
+//! let mut oset = Oset::new();
+//! assert_eq!(oset.insert("world"), true);
+//! assert_eq!(oset.insert("hello"), true);
+//! assert_eq!(oset.insert("world"), false); // Duplicate value: inserting fails
+//! assert_eq!(oset.iter().collect::<Vec<_>>(), [&"world", &"hello"]);
+//! ```
+
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::hash::Hash;
+use std::slice;
+
+use crate::collections::Uvec;
+
+// `Oset` ---------------------------------------------------------------------------------------------------
+
+/// A set that retains the insertion order of its elements.
+///
+/// For a basic example, see [the module documentation](crate::collections::oset).
+#[derive(Clone)]
+pub struct Oset<T>
+where
+  T: 'static,
+{
+  uvec: Uvec<'static, T, T>,
+}
+
+impl<T> Oset<T>
+where
+  T: Clone + Eq + Hash + 'static,
+{
+  /// Extracts a slice containing all elements in insertion order.
+  #[inline]
+  #[must_use]
+  pub fn as_slice(&self) -> &[T] { self.uvec.as_slice() }
+
+  /// Clears the set, removing all elements.
+  #[inline]
+  pub fn clear(&mut self) { self.uvec.clear(); }
+
+  /// Checks if the set contains a value.
+  #[inline]
+  #[must_use]
+  pub fn contains(&self, val: &T) -> bool { self.uvec.contains_key(val) }
+
+  /// Creates a new [`Oset`] containing the elements of this set that are not contained in `other`, in the
+  /// order of this set.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::collections::Oset;
+  ///
+  /// let oset = Oset::from([1, 2, 3, 4]);
+  /// assert_eq!(oset.difference(&Oset::from([4, 2, 5])).as_slice(), [1, 3]);
+  /// ```
+  #[must_use]
+  pub fn difference(&self, other: &Self) -> Self { Self { uvec: self.uvec.difference(&other.uvec) } }
+
+  /// Inserts a value at the end of the set.
+  ///
+  /// Returns whether the value has been inserted, i.e. `false` if the set already contains the value.
+  #[inline]
+  pub fn insert(&mut self, val: T) -> bool { self.uvec.push(val) }
+
+  /// Creates a new [`Oset`] containing the elements of this set that are also contained in `other`, in the
+  /// order of this set.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::collections::Oset;
+  ///
+  /// let oset = Oset::from([1, 2, 3, 4]);
+  /// assert_eq!(oset.intersection(&Oset::from([4, 2, 5])).as_slice(), [2, 4]);
+  /// ```
+  #[must_use]
+  pub fn intersection(&self, other: &Self) -> Self {
+    self.uvec.iter().filter(|val| other.contains(val)).cloned().collect()
+  }
+
+  /// Returns an iterator over the elements in insertion order.
+  #[inline]
+  pub fn iter(&self) -> slice::Iter<'_, T> { self.uvec.iter() }
+
+  /// Checks if the set is empty.
+  #[inline]
+  #[must_use]
+  pub fn is_empty(&self) -> bool { self.uvec.is_empty() }
+
+  /// Returns the number of elements in the set.
+  #[inline]
+  #[must_use]
+  pub fn len(&self) -> usize { self.uvec.len() }
+
+  /// Creates a new, empty [`Oset`].
+  #[inline]
+  #[must_use]
+  pub fn new() -> Self { Self { uvec: Uvec::new() } }
+
+  /// Removes a value from the set, retaining the order of the remaining elements.
+  ///
+  /// Returns whether the value has been removed, i.e. `false` if the set does not contain the value. As the
+  /// following elements are shifted, this takes linear time.
+  pub fn remove(&mut self, val: &T) -> bool {
+    if !self.contains(val) {
+      return false;
+    }
+    if let Some(index) = self.uvec.iter().position(|elem| elem == val) {
+      self.uvec.remove(index);
+    }
+    true
+  }
+
+  /// Creates a new [`Oset`] containing the elements of this set, followed by the elements of `other` that
+  /// are not contained in this set.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::collections::Oset;
+  ///
+  /// let oset = Oset::from([1, 2, 3]);
+  /// assert_eq!(oset.union(&Oset::from([4, 2, 5])).as_slice(), [1, 2, 3, 4, 5]);
+  /// ```
+  #[must_use]
+  pub fn union(&self, other: &Self) -> Self { Self { uvec: self.uvec.union(&other.uvec) } }
+}
+
+/// An [`Oset`] is formatted like a set, e.g. `{1, 2, 3}`, listing the elements in insertion order.
+impl<T> Debug for Oset<T>
+where
+  T: Debug + 'static,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.debug_set().entries(AsRef::<[T]>::as_ref(&self.uvec)).finish()
+  }
+}
+
+impl<T> Default for Oset<T>
+where
+  T: Clone + Eq + Hash + 'static,
+{
+  #[inline]
+  fn default() -> Self { Self::new() }
+}
+
+impl<T> Eq for Oset<T> where T: Clone + Eq + Hash + 'static {}
+
+impl<T> Extend<T> for Oset<T>
+where
+  T: Clone + Eq + Hash + 'static,
+{
+  #[inline]
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) { self.uvec.extend(iter); }
+}
+
+impl<T, const N: usize> From<[T; N]> for Oset<T>
+where
+  T: Clone + Eq + Hash + 'static,
+{
+  fn from(s: [T; N]) -> Self { Self { uvec: Uvec::from(s) } }
+}
+
+/// Collects an iterator into an [`Oset`], commonly called via [`Iterator::collect`].
+impl<T> FromIterator<T> for Oset<T>
+where
+  T: Clone + Eq + Hash + 'static,
+{
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self { Self { uvec: iter.into_iter().collect() } }
+}
+
+// `IntoIterator` for `Oset`
+impl<T> IntoIterator for Oset<T>
+where
+  T: Clone + Eq + Hash + 'static,
+{
+  type IntoIter = <Vec<T> as IntoIterator>::IntoIter;
+  type Item = <Vec<T> as IntoIterator>::Item;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter { self.uvec.into_iter() }
+}
+
+// `IntoIterator` for `&Oset`
+impl<'a, T> IntoIterator for &'a Oset<T>
+where
+  T: Clone + Eq + Hash + 'static,
+{
+  type IntoIter = slice::Iter<'a, T>;
+  type Item = &'a T;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+/// Two [`Oset`]s are equal if they contain the same elements, regardless of their order.
+impl<T> PartialEq for Oset<T>
+where
+  T: Clone + Eq + Hash + 'static,
+{
+  fn eq(&self, rhs: &Self) -> bool { self.len() == rhs.len() && self.iter().all(|val| rhs.contains(val)) }
+}
+
+// Tests ====================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // `Oset` -------------------------------------------------------------------------------------------------
+
+  #[test]
+  fn test_oset() {
+    let mut oset = Oset::new();
+    assert!(oset.is_empty());
+    assert!(oset.insert(3));
+    assert!(oset.insert(1));
+    assert!(oset.insert(2));
+    assert!(!oset.insert(1));
+    assert_eq!(oset.len(), 3);
+    assert_eq!(oset.as_slice(), [3, 1, 2]);
+    assert!(oset.contains(&1));
+    assert!(!oset.contains(&4));
+
+    assert!(oset.remove(&1));
+    assert!(!oset.remove(&1));
+    assert!(!oset.contains(&1));
+    assert_eq!(oset.as_slice(), [3, 2]);
+
+    // A removed value may be inserted again, at the end
+    assert!(oset.insert(1));
+    assert_eq!(oset.as_slice(), [3, 2, 1]);
+
+    oset.clear();
+    assert!(oset.is_empty());
+    assert!(oset.insert(1));
+  }
+
+  #[test]
+  fn test_oset_set_operations() {
+    let lhs = Oset::from(["c", "a", "d", "b"]);
+    let rhs = Oset::from(["e", "b", "c"]);
+    assert_eq!(lhs.union(&rhs).as_slice(), ["c", "a", "d", "b", "e"]);
+    assert_eq!(lhs.intersection(&rhs).as_slice(), ["c", "b"]);
+    assert_eq!(rhs.intersection(&lhs).as_slice(), ["b", "c"]);
+    assert_eq!(lhs.difference(&rhs).as_slice(), ["a", "d"]);
+    assert_eq!(rhs.difference(&lhs).as_slice(), ["e"]);
+    assert!(lhs.intersection(&Oset::new()).is_empty());
+  }
+
+  #[test]
+  fn test_oset_traits() {
+    let mut oset: Oset<i32> = [2, 1, 2, 3].into_iter().collect();
+    assert_eq!(format!("{oset:?}"), "{2, 1, 3}");
+    oset.extend([4, 1, 5]);
+    assert_eq!(oset.as_slice(), [2, 1, 3, 4, 5]);
+
+    assert_eq!(oset, Oset::from([5, 4, 3, 2, 1]));
+    assert_ne!(oset, Oset::from([1, 2, 3, 4]));
+    assert_eq!(Oset::<i32>::default(), Oset::new());
+
+    assert_eq!((&oset).into_iter().copied().collect::<Vec<_>>(), [2, 1, 3, 4, 5]);
+    assert_eq!(oset.clone().into_iter().collect::<Vec<_>>(), [2, 1, 3, 4, 5]);
+  }
+}
+
+// EOF
@@ -45,12 +45,27 @@ use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Debug;
+use std::fmt::Display;
 use std::fmt::Formatter;
 use std::hash::Hash;
+use std::mem;
 use std::ops::Deref;
+use std::ops::DerefMut;
 use std::ops::Index;
+use std::slice;
 use std::slice::SliceIndex;
 
+#[cfg(feature = "indexmap")]
+use indexmap::IndexSet;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Deserializer;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::Serializer;
+
 // `Uvec` ---------------------------------------------------------------------------------------------------
 
 /// A [`Uvec`] behaves very much like a [`Vec`], but it can only contain unique elements.
@@ -72,12 +87,118 @@ where
   #[must_use]
   pub fn as_slice(&self) -> &[V] { self.vec.as_slice() }
 
+  /// Binary searches this vector for a given value.
+  ///
+  /// This delegates to [`slice::binary_search`]. The vector must be sorted, otherwise the returned result is
+  /// unspecified and meaningless. Since the vector is only read, its keys are untouched.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Err`] containing the index where a matching value could be inserted while maintaining sorted
+  /// order if the value is not found.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::collections::Uvec;
+  ///
+  /// let uvec = Uvec::from([1, 3, 5, 7]);
+  /// assert_eq!(uvec.binary_search(&5), Ok(2));
+  /// assert_eq!(uvec.binary_search(&4), Err(2));
+  /// ```
+  pub fn binary_search(&self, val: &V) -> Result<usize, usize>
+  where
+    V: Ord, {
+    self.vec.binary_search(val)
+  }
+
+  /// Binary searches this vector with a comparator function.
+  ///
+  /// This delegates to [`slice::binary_search_by`]. The vector must be sorted in an order consistent with
+  /// `f`, otherwise the returned result is unspecified and meaningless. Since the vector is only read, its
+  /// keys are untouched.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Err`] containing the index where a matching value could be inserted while maintaining sorted
+  /// order if no value is found.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::collections::Uvec;
+  ///
+  /// let uvec = Uvec::from(["a", "bb", "ccc"]);
+  /// assert_eq!(uvec.binary_search_by(|val| val.len().cmp(&2)), Ok(1));
+  /// assert_eq!(uvec.binary_search_by(|val| val.len().cmp(&4)), Err(3));
+  /// ```
+  pub fn binary_search_by<F>(&self, f: F) -> Result<usize, usize>
+  where
+    F: FnMut(&V) -> Ordering, {
+    self.vec.binary_search_by(f)
+  }
+
   /// Clears the vector, removing all elements.
   pub fn clear(&mut self) {
     self.set.clear();
     self.vec.clear();
   }
 
+  /// Returns an adapter that implements [`Debug`] by formatting the values of the vector as well as its set
+  /// of keys.
+  ///
+  /// This is useful for debugging key-generating functions. The keys are listed in no particular order.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::collections::Uvec;
+  ///
+  /// let uvec = Uvec::from([1, 1]);
+  /// assert_eq!(format!("{uvec:?}"), "[1]");
+  /// assert_eq!(format!("{:?}", uvec.debug_keys()), "Uvec { values: [1], keys: {1} }");
+  /// ```
+  #[inline]
+  #[must_use]
+  pub fn debug_keys(&self) -> DebugKeys<'_, 'a, K, V>
+  where
+    K: Debug,
+    V: Debug, {
+    DebugKeys { uvec: self }
+  }
+
+  /// Checks if a value with the given key is contained in the vector.
+  #[inline]
+  pub(crate) fn contains_key(&self, key: &K) -> bool { self.set.contains(key) }
+
+  /// Creates a new [`Uvec`] containing the values of this vector whose keys are not generated by any value
+  /// of `other`.
+  ///
+  /// All keys, including those of `other`'s values, are generated by this vector's key-generating function,
+  /// which is also used by the returned [`Uvec`].
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::collections::Uvec;
+  ///
+  /// let uvec = Uvec::from([1, 2, 3, 4]);
+  /// assert_eq!(uvec.difference(&Uvec::from([4, 2, 5])), Uvec::from([1, 3]));
+  /// ```
+  #[must_use]
+  pub fn difference(&self, other: &Self) -> Self
+  where
+    V: Clone, {
+    let other_keys: HashSet<K> = other.vec.iter().filter_map(self.key).collect();
+    let mut ret = Uvec::with_key(self.key);
+    for val in &self.vec {
+      if let Some(key) = (self.key)(val) && !other_keys.contains(&key) {
+        ret.push(val.clone());
+      }
+    }
+    ret
+  }
+
   /// Inserts a value at position `index` within the vector, shifting all elements after it to the right.
   ///
   /// Returns whether the operation succeeds.
@@ -109,6 +230,42 @@ where
     false
   }
 
+  /// Returns an iterator over the vector.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::collections::Uvec;
+  ///
+  /// let uvec = Uvec::from([1, 2, 3]);
+  /// assert_eq!(uvec.iter().sum::<i32>(), 6);
+  /// ```
+  #[inline]
+  pub fn iter(&self) -> slice::Iter<'_, V> { self.vec.iter() }
+
+  /// Returns a guard that allows modifying each value of the vector.
+  ///
+  /// Modifying a value may change its key. Therefore, when the guard is dropped, the keys of all values are
+  /// regenerated. **A value is removed from the vector if no key can be generated for it, or if its new key
+  /// equals the key of a preceding value.**
+  ///
+  /// The guard derefs to a mutable slice, and a mutable reference to the guard can be iterated over.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::collections::Uvec;
+  ///
+  /// let mut uvec = Uvec::from([1, 2, 3]);
+  /// for val in &mut uvec.iter_mut() {
+  ///   *val /= 2;
+  /// }
+  /// assert_eq!(uvec, Uvec::from([0, 1])); // `3 / 2` equals `2 / 2`, so the last value is removed
+  /// ```
+  #[allow(clippy::iter_not_returning_iterator)]
+  #[inline]
+  pub fn iter_mut(&mut self) -> IterMutGuard<'_, 'a, K, V> { IterMutGuard { uvec: self } }
+
   /// Checks if the vector contains no elements.
   #[inline]
   #[must_use]
@@ -119,6 +276,30 @@ where
   #[must_use]
   pub fn len(&self) -> usize { self.vec.len() }
 
+  /// Consumes the vector and maps each value into a new [`Uvec`] with a different key-generating function.
+  ///
+  /// The mapped values are pushed in order, see [`push`](Uvec::push). Hence, the resulting vector may be
+  /// shorter than this one: If the new key-generating function returns equal keys for several mapped values,
+  /// only the first of them is kept, and if it returns [`None`], the mapped value is dropped.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::collections::Uvec;
+  ///
+  /// let uvec = Uvec::from([1, -1, 2, -3]);
+  /// let abs = uvec.map(|val| val * 10, &|val: &i32| Some(val.abs()));
+  /// assert_eq!(abs.as_slice(), [10, 20, -30]);
+  /// ```
+  pub fn map<K2, V2, F>(self, value_fn: F, key: &dyn Fn(&V2) -> Option<K2>) -> Uvec<'_, K2, V2>
+  where
+    K2: Eq + Hash,
+    F: FnMut(V) -> V2, {
+    let mut ret = Uvec::with_key(key);
+    ret.extend(self.vec.into_iter().map(value_fn));
+    ret
+  }
+
   /// Removes the last element from a vector and returns it, or [`None`] if it is empty.
   ///
   /// # Examples
@@ -141,6 +322,29 @@ where
     }
   }
 
+  /// Inserts values at the front of the vector, retaining their order.
+  ///
+  /// The inserted values take precedence over the existing ones: Of all values with equal keys, the one
+  /// closest to the front of the resulting vector wins. So if an inserted value has the same key as an
+  /// existing value, the existing value is removed. If several inserted values have equal keys, only the
+  /// first of them is inserted.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::collections::Uvec;
+  ///
+  /// let mut uvec = Uvec::from([3, 4, 5]);
+  /// uvec.prepend([1, 4, 2, 1]);
+  /// assert_eq!(uvec, Uvec::from([1, 4, 2, 3, 5]));
+  /// ```
+  pub fn prepend<I: IntoIterator<Item = V>>(&mut self, iter: I) {
+    let mut ret = Uvec::with_key(self.key);
+    ret.extend(iter);
+    ret.extend(mem::take(&mut self.vec));
+    *self = ret;
+  }
+
   /// Appends a value to the back of the vector.
   ///
   /// Returns whether the operation succeeds.
@@ -165,12 +369,69 @@ where
     ret
   }
 
+  /// Regenerates the keys of all values, removing values that have no or duplicate keys.
+  fn regenerate_keys(&mut self) {
+    let set = &mut self.set;
+    let key = self.key;
+    set.clear();
+    self.vec.retain(|val| if let Some(key) = key(val) { set.insert(key) } else { false });
+  }
+
   fn remove_from_set(&mut self, val: &V) {
     let key = (self.key)(val);
     let result = self.set.remove(&key.unwrap());
     debug_assert!(result);
   }
 
+  /// Shortens the vector, keeping the first `len` elements and dropping the rest.
+  ///
+  /// If `len` is greater than or equal to the vector's current length, this has no effect. The keys of the
+  /// dropped elements are removed as well, so equal values may be inserted again afterwards.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::collections::Uvec;
+  ///
+  /// let mut uvec = Uvec::from([1, 2, 3, 4]);
+  /// uvec.truncate(2);
+  /// assert_eq!(uvec, Uvec::from([1, 2]));
+  /// assert_eq!(uvec.push(3), true);
+  /// ```
+  pub fn truncate(&mut self, len: usize) {
+    if len == 0 {
+      self.clear();
+    } else if len < self.len() {
+      for val in self.vec.split_off(len) {
+        self.remove_from_set(&val);
+      }
+    }
+  }
+
+  /// Creates a new [`Uvec`] containing the values of this vector, followed by the values of `other` whose
+  /// keys are not generated by any value of this vector.
+  ///
+  /// All keys, including those of `other`'s values, are generated by this vector's key-generating function,
+  /// which is also used by the returned [`Uvec`].
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::collections::Uvec;
+  ///
+  /// let uvec = Uvec::from([1, 2, 3]);
+  /// assert_eq!(uvec.union(&Uvec::from([4, 2, 5])), Uvec::from([1, 2, 3, 4, 5]));
+  /// ```
+  #[must_use]
+  pub fn union(&self, other: &Self) -> Self
+  where
+    V: Clone, {
+    let mut ret = Uvec::with_key(self.key);
+    ret.extend(self.vec.iter().cloned());
+    ret.extend(other.vec.iter().cloned());
+    ret
+  }
+
   /// Creates a new [`Uvec`] with a key-generating function.
   ///
   /// # Examples
@@ -225,6 +486,26 @@ where
   #[inline]
   #[must_use]
   pub fn new() -> Self { Self { set: HashSet::new(), vec: Vec::new(), key: &|val: &V| Some(val.clone()) } }
+
+  /// Converts the vector into an [`IndexSet`], preserving the order of the elements.
+  ///
+  /// This is lossless, since both collections contain unique elements in insertion order.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::collections::Uvec;
+  ///
+  /// let set = Uvec::from([3, 1, 2]).into_index_set();
+  /// assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![3, 1, 2]);
+  /// ```
+  #[cfg(feature = "indexmap")]
+  #[must_use]
+  pub fn into_index_set(self) -> IndexSet<V>
+  where
+    V: Eq + Hash, {
+    self.vec.into_iter().collect()
+  }
 }
 
 impl<K, V> AsRef<[V]> for Uvec<'_, K, V> {
@@ -237,6 +518,10 @@ impl<K, V> AsRef<Vec<V>> for Uvec<'_, K, V> {
   fn as_ref(&self) -> &Vec<V> { &self.vec }
 }
 
+/// Formats the values of the vector, like a [`Vec`].
+///
+/// The keys are not formatted, so `K` need not implement [`Debug`]. Use [`Uvec::debug_keys`] to format the
+/// keys as well.
 impl<K, V> Debug for Uvec<'_, K, V>
 where
   V: Debug,
@@ -265,6 +550,43 @@ where
   fn deref(&self) -> &Self::Target { self.as_slice() }
 }
 
+/// A [`Uvec`] implements [`Deserialize`] if the types `K` and `V` are identical, because only then can the
+/// key-generating function be supplied automatically.
+///
+/// The [`Uvec`] is deserialized from a sequence. Duplicates in the input are silently dropped, retaining the
+/// first occurrence.
+#[allow(clippy::mismatching_type_param_order)]
+#[cfg(feature = "serde")]
+impl<'de, V> Deserialize<'de> for Uvec<'_, V, V>
+where
+  V: Clone + Deserialize<'de> + Eq + Hash,
+{
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    Vec::<V>::deserialize(deserializer).map(Self::from_iter)
+  }
+}
+
+/// A [`Uvec`] is displayed like a JSON array, e.g. `[1, 2, 3]`, using the [`Display`] implementation of each
+/// element.
+///
+/// This differs from the [`Debug`] implementation, which delegates to the inner [`Vec`] and uses the
+/// [`Debug`] implementation of each element.
+impl<K, V> Display for Uvec<'_, K, V>
+where
+  V: Display,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.write_str("[")?;
+    for (i, val) in self.vec.iter().enumerate() {
+      if i > 0 {
+        f.write_str(", ")?;
+      }
+      val.fmt(f)?;
+    }
+    f.write_str("]")
+  }
+}
+
 impl<K, V> Eq for Uvec<'_, K, V> where V: Eq {}
 
 impl<K, V> Extend<V> for Uvec<'_, K, V>
@@ -293,6 +615,16 @@ where
   }
 }
 
+/// Converts an [`IndexSet`] into a [`Uvec`], preserving the order of the elements.
+#[allow(clippy::mismatching_type_param_order)]
+#[cfg(feature = "indexmap")]
+impl<V, S> From<IndexSet<V, S>> for Uvec<'_, V, V>
+where
+  V: Clone + Eq + Hash,
+{
+  fn from(s: IndexSet<V, S>) -> Self { s.into_iter().collect() }
+}
+
 /// Collects an iterator into a [`Uvec`], commonly called via [`Iterator::collect`].
 #[allow(clippy::mismatching_type_param_order)]
 impl<V> FromIterator<V> for Uvec<'_, V, V>
@@ -332,7 +664,6 @@ where
 }
 
 // `IntoIterator` for `&Uvec`
-#[allow(clippy::into_iter_without_iter)]
 impl<'a, K, V> IntoIterator for &'a Uvec<'a, K, V> {
   type IntoIter = <&'a Vec<V> as IntoIterator>::IntoIter;
   type Item = <&'a Vec<V> as IntoIterator>::Item;
@@ -365,6 +696,211 @@ where
   fn partial_cmp(&self, rhs: &Uvec<'a, K, V>) -> Option<Ordering> { self.vec.partial_cmp(&rhs.vec) }
 }
 
+/// A [`Uvec`] is serialized as a sequence of its elements.
+#[cfg(feature = "serde")]
+impl<K, V> Serialize for Uvec<'_, K, V>
+where
+  V: Serialize,
+{
+  #[inline]
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    self.vec.serialize(serializer)
+  }
+}
+
+// `BoundedUvec` --------------------------------------------------------------------------------------------
+
+/// A [`Uvec`] with a maximum length that evicts its oldest element to make room for a new one.
+///
+/// Eviction follows the FIFO principle: If the vector is full, [`push`](BoundedUvec::push) removes the
+/// element at the front, which is the one pushed first, before appending the new value to the back. As with
+/// [`Uvec`], duplicates are rejected. A rejected value does not evict any element. Since elements are
+/// stored in a [`Vec`], evicting an element takes O(n) time, where n is the maximum length.
+///
+/// A [`BoundedUvec`] dereferences to its [`Uvec`], so all read-only operations of [`Uvec`] are available.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::collections::BoundedUvec;
+/// use meadows::collections::PushResult;
+///
+/// let mut uvec = BoundedUvec::new(2);
+/// assert_eq!(uvec.push(1), PushResult::Inserted);
+/// assert_eq!(uvec.push(2), PushResult::Inserted);
+/// assert_eq!(uvec.push(2), PushResult::Duplicate);
+/// assert_eq!(uvec.push(3), PushResult::InsertedEvicting(1));
+/// assert_eq!(uvec.as_slice(), [2, 3]);
+/// ```
+#[derive(Clone)]
+pub struct BoundedUvec<'a, K, V> {
+  max_len: usize,
+  uvec: Uvec<'a, K, V>,
+}
+
+impl<'a, K, V> BoundedUvec<'a, K, V>
+where
+  K: Eq + Hash,
+{
+  /// Converts this vector into a [`Uvec`] without a maximum length.
+  #[inline]
+  #[must_use]
+  pub fn into_uvec(self) -> Uvec<'a, K, V> { self.uvec }
+
+  /// Returns the maximum length of the vector.
+  #[inline]
+  #[must_use]
+  pub fn max_len(&self) -> usize { self.max_len }
+
+  /// Appends a value to the back of the vector, evicting the element at the front if the vector is full.
+  ///
+  /// Returns what has happened, see [`PushResult`].
+  pub fn push(&mut self, val: V) -> PushResult<V> {
+    let Some(key) = (self.uvec.key)(&val) else {
+      return PushResult::NoKey;
+    };
+    if self.uvec.set.contains(&key) {
+      return PushResult::Duplicate;
+    }
+
+    let evicted = (self.uvec.len() == self.max_len).then(|| self.uvec.remove(0));
+    self.uvec.set.insert(key);
+    self.uvec.vec.push(val);
+    match evicted {
+      Some(evicted) => PushResult::InsertedEvicting(evicted),
+      None => PushResult::Inserted,
+    }
+  }
+
+  /// Creates a new, empty [`BoundedUvec`] with a maximum length and a key-generating function, see
+  /// [`Uvec::with_key`].
+  ///
+  /// # Panics
+  ///
+  /// Panics if `max_len` is 0.
+  #[must_use]
+  pub fn with_key(max_len: usize, key: &'a dyn Fn(&V) -> Option<K>) -> Self {
+    assert!(max_len > 0, "The maximum length must be greater than 0");
+    Self { max_len, uvec: Uvec::with_key(key) }
+  }
+}
+
+/// If the types `K` and `V` are identical, a [`BoundedUvec`] may be created using the
+/// [`new`](BoundedUvec::new) function.
+#[allow(clippy::mismatching_type_param_order)]
+impl<V> BoundedUvec<'_, V, V>
+where
+  V: Clone,
+{
+  /// Creates a new, empty [`BoundedUvec`] with a maximum length, automatically supplying a key-generating
+  /// function, see [`Uvec::new`].
+  ///
+  /// # Panics
+  ///
+  /// Panics if `max_len` is 0.
+  #[must_use]
+  pub fn new(max_len: usize) -> Self {
+    assert!(max_len > 0, "The maximum length must be greater than 0");
+    Self { max_len, uvec: Uvec::new() }
+  }
+}
+
+impl<K, V> Debug for BoundedUvec<'_, K, V>
+where
+  V: Debug,
+{
+  #[inline]
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { self.uvec.fmt(f) }
+}
+
+impl<'a, K, V> Deref for BoundedUvec<'a, K, V> {
+  type Target = Uvec<'a, K, V>;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target { &self.uvec }
+}
+
+// `PushResult` ---------------------------------------------------------------------------------------------
+
+/// The result of [`BoundedUvec::push`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PushResult<V> {
+  /// The value has been appended.
+  Inserted,
+  /// The value has been appended, and the contained element, which was at the front, has been evicted.
+  InsertedEvicting(V),
+  /// The value has been rejected because the vector already contains a value with the same key.
+  Duplicate,
+  /// The value has been rejected because no key has been generated for it.
+  NoKey,
+}
+
+// `DebugKeys` ----------------------------------------------------------------------------------------------
+
+/// An adapter that formats the values and keys of a [`Uvec`], created by [`Uvec::debug_keys`].
+pub struct DebugKeys<'b, 'a, K, V> {
+  uvec: &'b Uvec<'a, K, V>,
+}
+
+impl<K, V> Debug for DebugKeys<'_, '_, K, V>
+where
+  K: Debug,
+  V: Debug,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Uvec").field("values", &self.uvec.vec).field("keys", &self.uvec.set).finish()
+  }
+}
+
+// `IterMutGuard` -------------------------------------------------------------------------------------------
+
+/// A guard that allows modifying each value of a [`Uvec`], created by [`Uvec::iter_mut`].
+///
+/// When the guard is dropped, the keys of all values are regenerated.
+pub struct IterMutGuard<'b, 'a, K, V>
+where
+  K: Eq + Hash,
+{
+  uvec: &'b mut Uvec<'a, K, V>,
+}
+
+impl<K, V> Deref for IterMutGuard<'_, '_, K, V>
+where
+  K: Eq + Hash,
+{
+  type Target = [V];
+
+  #[inline]
+  fn deref(&self) -> &Self::Target { &self.uvec.vec }
+}
+
+impl<K, V> DerefMut for IterMutGuard<'_, '_, K, V>
+where
+  K: Eq + Hash,
+{
+  #[inline]
+  fn deref_mut(&mut self) -> &mut Self::Target { &mut self.uvec.vec }
+}
+
+impl<K, V> Drop for IterMutGuard<'_, '_, K, V>
+where
+  K: Eq + Hash,
+{
+  fn drop(&mut self) { self.uvec.regenerate_keys(); }
+}
+
+// `IntoIterator` for `&mut IterMutGuard`
+impl<'r, K, V> IntoIterator for &'r mut IterMutGuard<'_, '_, K, V>
+where
+  K: Eq + Hash,
+{
+  type IntoIter = slice::IterMut<'r, V>;
+  type Item = &'r mut V;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter { self.uvec.vec.iter_mut() }
+}
+
 // Tests ====================================================================================================
 
 #[cfg(test)]
@@ -374,8 +910,59 @@ mod tests {
 
   use super::*;
 
+  // `BoundedUvec` ------------------------------------------------------------------------------------------
+
+  #[test]
+  fn test_bounded_uvec_push() {
+    let mut uvec = BoundedUvec::new(3);
+    assert_eq!(uvec.max_len(), 3);
+
+    // Fill to capacity
+    for val in 1..=3 {
+      assert_eq!(uvec.push(val), PushResult::Inserted);
+    }
+    assert_eq!(uvec.as_slice(), [1, 2, 3]);
+
+    // Duplicates are rejected without evicting
+    assert_eq!(uvec.push(1), PushResult::Duplicate);
+    assert_eq!(uvec.as_slice(), [1, 2, 3]);
+
+    // Evict the oldest element
+    assert_eq!(uvec.push(4), PushResult::InsertedEvicting(1));
+    assert_eq!(uvec.push(5), PushResult::InsertedEvicting(2));
+    assert_eq!(uvec.as_slice(), [3, 4, 5]);
+    assert_eq!(uvec.uvec.set.len(), 3);
+
+    // An evicted value may be pushed again
+    assert_eq!(uvec.push(1), PushResult::InsertedEvicting(3));
+    assert_eq!(uvec.into_uvec(), Uvec::from([4, 5, 1]));
+
+    let key = |val: &i32| (*val >= 0).then_some(*val);
+    let mut uvec = BoundedUvec::with_key(1, &key);
+    assert_eq!(uvec.push(-1), PushResult::NoKey);
+    assert_eq!(uvec.push(1), PushResult::Inserted);
+    assert_eq!(uvec.push(2), PushResult::InsertedEvicting(1));
+  }
+
+  #[test]
+  #[should_panic(expected = "The maximum length must be greater than 0")]
+  fn test_bounded_uvec_new_fail() { let _ = BoundedUvec::<i32, i32>::new(0); }
+
   // `Uvec` -------------------------------------------------------------------------------------------------
 
+  #[test]
+  fn test_uvec_binary_search() {
+    let uvec = Uvec::from([1, 3, 5, 7, 9]);
+    let slice = [1, 3, 5, 7, 9];
+    for val in 0..=10 {
+      assert_eq!(uvec.binary_search(&val), slice.binary_search(&val));
+      let f = |probe: &i32| probe.cmp(&val);
+      assert_eq!(uvec.binary_search_by(f), slice.binary_search_by(f));
+    }
+    assert_eq!(uvec.binary_search(&5), Ok(2));
+    assert_eq!(uvec.binary_search(&6), Err(3));
+  }
+
   #[test]
   fn test_uvec_clear() {
     let mut uvec = Uvec::from([1, 2, 3, 2, 1]);
@@ -386,6 +973,27 @@ mod tests {
     assert_eq!(uvec.vec.len(), 0);
   }
 
+  #[test]
+  fn test_uvec_difference() {
+    let mut lhs = Uvec::with_key(&|val: &i32| Some(val.to_string()));
+    lhs.extend([1, 2, 3, 4]);
+    let mut rhs = Uvec::with_key(&|val: &i32| Some(val.to_string()));
+    rhs.extend([5, 4, 2]);
+
+    let uvec = lhs.difference(&rhs);
+    assert_eq!(uvec.set, HashSet::from(["1".to_string(), "3".to_string()]));
+    assert_eq!(uvec.vec, vec![1, 3]);
+
+    let uvec = rhs.difference(&lhs);
+    assert_eq!(uvec.vec, vec![5]);
+
+    // `other`'s keys are generated by `self`'s key-generating function
+    let mut rhs = Uvec::with_key(&|val: &i32| Some((val % 2).to_string()));
+    rhs.extend([5, 4, 2]);
+    assert_eq!(rhs.vec, vec![5, 4]);
+    assert_eq!(lhs.difference(&rhs).vec, vec![1, 2, 3]);
+  }
+
   #[test]
   fn test_uvec_is_empty() {
     let mut uvec = Uvec::from([1, 2, 3, 2, 1]);
@@ -394,6 +1002,54 @@ mod tests {
     assert!(uvec.is_empty());
   }
 
+  #[test]
+  fn test_uvec_iter() {
+    let uvec = Uvec::from([1, 2, 3, 2, 1]);
+    assert_eq!(uvec.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(uvec.iter().len(), 3);
+  }
+
+  #[test]
+  fn test_uvec_iter_mut() {
+    let mut uvec = Uvec::from([1, 2, 3]);
+
+    // Keys change, but stay unique
+    for val in &mut uvec.iter_mut() {
+      *val *= 10;
+    }
+    assert_eq!(uvec.set, HashSet::from([10, 20, 30]));
+    assert_eq!(uvec.vec, vec![10, 20, 30]);
+    assert!(uvec.push(1));
+    assert!(!uvec.push(10));
+
+    // Keys collide: the first value wins
+    uvec.iter_mut()[1] = 30;
+    assert_eq!(uvec.set, HashSet::from([1, 10, 30]));
+    assert_eq!(uvec.vec, vec![10, 30, 1]);
+
+    // No keys generated
+    let mut uvec = Uvec::with_key(&|val: &i32| if *val > 0 { Some(*val) } else { None });
+    uvec.extend([1, 2, 3]);
+    uvec.iter_mut().iter_mut().for_each(|val| *val -= 2);
+    assert_eq!(uvec.set, HashSet::from([1]));
+    assert_eq!(uvec.vec, vec![1]);
+  }
+
+  #[test]
+  fn test_uvec_map() {
+    let uvec = Uvec::from([3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5]);
+    assert_eq!(uvec.as_slice(), [3, 1, 4, 5, 9, 2, 6]);
+
+    let key = |val: &String| Some(val.clone());
+    let strings = uvec.clone().map(|val| val.to_string(), &key);
+    assert_eq!(strings.as_slice(), ["3", "1", "4", "5", "9", "2", "6"]);
+
+    // Colliding and missing keys shrink the result
+    let key = |val: &String| (val != "odd").then(|| val.clone());
+    let parities = uvec.map(|val| String::from(if val % 2 == 0 { "even" } else { "odd" }), &key);
+    assert_eq!(parities.as_slice(), ["even"]);
+  }
+
   #[test]
   fn test_uvec_new() {
     let mut uvec = Uvec::new();
@@ -407,6 +1063,58 @@ mod tests {
     assert_eq!(uvec.vec, vec![1, 2, 3]);
   }
 
+  #[test]
+  fn test_uvec_prepend() {
+    let mut uvec = Uvec::with_key(&|val: &i32| Some(val.to_string()));
+    uvec.extend([3, 4, 5]);
+
+    // The inserted `4` wins over the existing one, the second inserted `1` is dropped
+    uvec.prepend([1, 4, 2, 1]);
+    assert_eq!(uvec.set.len(), 5);
+    assert_eq!(uvec.vec, vec![1, 4, 2, 3, 5]);
+
+    uvec.prepend([]);
+    assert_eq!(uvec.vec, vec![1, 4, 2, 3, 5]);
+
+    uvec.prepend([5]);
+    assert_eq!(uvec.vec, vec![5, 1, 4, 2, 3]);
+    assert!(!uvec.push(5));
+  }
+
+  #[test]
+  fn test_uvec_truncate() {
+    let mut uvec = Uvec::from([1, 2, 3, 4]);
+    uvec.truncate(4);
+    assert_eq!(uvec.vec, vec![1, 2, 3, 4]);
+
+    uvec.truncate(2);
+    assert_eq!(uvec.set, HashSet::from([1, 2]));
+    assert_eq!(uvec.vec, vec![1, 2]);
+    assert!(uvec.push(3));
+    assert!(!uvec.push(2));
+    assert_eq!(uvec.vec, vec![1, 2, 3]);
+
+    uvec.truncate(0);
+    assert!(uvec.set.is_empty());
+    assert!(uvec.vec.is_empty());
+  }
+
+  #[test]
+  fn test_uvec_union() {
+    let mut lhs = Uvec::with_key(&|val: &i32| Some(val.to_string()));
+    lhs.extend([1, 2, 3]);
+    let mut rhs = Uvec::with_key(&|val: &i32| Some(val.to_string()));
+    rhs.extend([5, 3, 4, 1]);
+
+    let mut uvec = lhs.union(&rhs);
+    assert_eq!(uvec.set.len(), 5);
+    assert_eq!(uvec.vec, vec![1, 2, 3, 5, 4]);
+    assert!(!uvec.push(4));
+
+    let uvec = rhs.union(&lhs);
+    assert_eq!(uvec.vec, vec![5, 3, 4, 1, 2]);
+  }
+
   #[test]
   fn test_uvec_with_key_to_string() {
     let mut uvec = Uvec::with_key(&|val: &i32| Some(val.to_string()));
@@ -453,8 +1161,34 @@ mod tests {
 
   #[test]
   fn test_debug_for_uvec() {
+    #[derive(Eq, Hash, PartialEq)]
+    struct Key(i32);
+
     let uvec = Uvec::from([1, 2, 3, 2, 1]);
     assert_eq!(format!("{:?}", uvec), "[1, 2, 3]");
+
+    let key = |val: &i32| Some(val.to_string());
+    let mut uvec = Uvec::with_key(&key);
+    uvec.extend([42, 42]);
+    assert_eq!(format!("{:?}", uvec), "[42]");
+    assert_eq!(format!("{:#?}", uvec), "[\n    42,\n]");
+    assert_eq!(format!("{:#?}", uvec.debug_keys()), [
+      "Uvec {\n",
+      "    values: [\n",
+      "        42,\n",
+      "    ],\n",
+      "    keys: {\n",
+      "        \"42\",\n",
+      "    },\n",
+      "}",
+    ]
+    .concat());
+
+    // Keys need not implement `Debug`
+    let key = |val: &i32| Some(Key(*val));
+    let mut uvec = Uvec::with_key(&key);
+    uvec.extend([1, 2, 1]);
+    assert_eq!(format!("{:?}", uvec), "[1, 2]");
   }
 
   #[test]
@@ -464,6 +1198,37 @@ mod tests {
     assert_eq!(other, &[1, 2, 3]);
   }
 
+  #[cfg(feature = "serde")]
+  #[test]
+  fn test_deserialize_for_uvec() -> serde_json::Result<()> {
+    let uvec: Uvec<i32, i32> = serde_json::from_str("[1, 2, 3, 2, 1]")?;
+    assert_eq!(uvec.set, HashSet::from([1, 2, 3]));
+    assert_eq!(uvec.vec, vec![1, 2, 3]);
+    Ok(())
+  }
+
+  #[test]
+  fn test_display_for_uvec() {
+    let uvec = Uvec::from([1, 2, 3, 2, 1]);
+    assert_eq!(format!("{}", uvec), "[1, 2, 3]");
+    assert_eq!(format!("{}", Uvec::from(["a", "b"])), "[a, b]");
+    assert_eq!(format!("{}", Uvec::<i32, i32>::new()), "[]");
+  }
+
+  #[cfg(feature = "indexmap")]
+  #[test]
+  fn test_from_index_set_for_uvec() {
+    let set = IndexSet::from(["c", "a", "b"]);
+    let uvec = Uvec::from(set);
+    assert_eq!(uvec.set, HashSet::from(["a", "b", "c"]));
+    assert_eq!(uvec.vec, vec!["c", "a", "b"]);
+
+    // Round trip. Compare as vectors, since comparing `IndexSet`s ignores the order
+    assert_eq!(uvec.into_index_set().into_iter().collect::<Vec<_>>(), vec!["c", "a", "b"]);
+    let other = Uvec::from([3, 1, 2, 1]);
+    assert_eq!(Uvec::from(other.clone().into_index_set()), other);
+  }
+
   #[test]
   fn test_from_iter_for_uvec() {
     let uvec: Uvec<_, _> = [1, 2, 3, 2, 1].into_iter().collect();
@@ -508,6 +1273,24 @@ mod tests {
       n += 1;
     }
   }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn test_serialize_for_uvec() -> serde_json::Result<()> {
+    let uvec = Uvec::from(["a", "b", "c", "b", "a"]);
+    let json = serde_json::to_string(&uvec)?;
+    assert_eq!(json, r#"["a","b","c"]"#);
+
+    let other: Uvec<String, String> = serde_json::from_str(&json)?;
+    assert_eq!(other.vec, vec!["a", "b", "c"]);
+
+    // Keys generated by a custom key-generating function are not serialized
+    let mut uvec = Uvec::with_key(&|val: &i32| Some(val.to_string()));
+    uvec.extend([1, 2, 3]);
+    assert_eq!(serde_json::to_string(&uvec)?, "[1,2,3]");
+
+    Ok(())
+  }
 }
 
 // EOF
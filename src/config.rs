@@ -2,29 +2,54 @@
 
 //! Configuration-related utilities.
 
+use std::borrow::Cow;
+use std::collections::HashSet;
 use std::ffi::OsStr;
+use std::ffi::OsString;
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fs;
 use std::io;
 use std::io::StdoutLock;
 use std::io::prelude::*;
+use std::path::Path;
 use std::path::PathBuf;
 use std::process;
 use std::sync::OnceLock;
+#[cfg(feature = "notify")]
+use std::sync::mpsc;
+#[cfg(feature = "notify")]
+use std::thread;
+#[cfg(feature = "notify")]
+use std::time::Duration;
+#[cfg(feature = "notify")]
+use std::time::Instant;
 
 use anstream::AutoStream;
 use thiserror::Error as ThisError;
 
-use crate::collections::Uvec;
+use crate::env::ExpandError;
 use crate::process::ExecType;
 
+// Constants ------------------------------------------------------------------------------------------------
+
+/// The quiet period after which [`watch`] reports a change.
+#[cfg(feature = "notify")]
+pub const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
 // Macros ---------------------------------------------------------------------------------------------------
 
-/// Evaluates to an [`io::Result<()>`].
+/// Outputs debug information to a [`DebugSink`]. Evaluates to an [`io::Result<()>`].
 macro_rules! debug {
-  ($stream:expr, $($arg:tt)+) => {{
-    if let Some(stream) = $stream {
-      writeln!(stream, "[meadows::config] {}", format_args!($($arg)+))
-    } else {
-      Ok(())
+  ($sink:expr, $($arg:tt)+) => {{
+    match $sink {
+      DebugSink::Off => Ok(()),
+      DebugSink::Stdout(stream) => writeln!(stream, "[meadows::config] {}", format_args!($($arg)+)),
+      DebugSink::Tracing => {
+        tracing::debug!(target: "meadows::config", "{}", format_args!($($arg)+));
+        Ok(())
+      }
     }
   }}
 }
@@ -33,6 +58,549 @@ macro_rules! debug {
 
 type AutoStreamStdoutLock = AutoStream<StdoutLock<'static>>;
 
+/// A function called for each path probed, see [`ConfigFinder::on_probe`].
+///
+/// This is a shared reference to an [`Fn`] rather than an [`FnMut`] so that [`ConfigFinder`] stays [`Clone`]
+/// and covariant in its lifetime, e.g. a `ConfigFinder<'static>` may be given a function borrowing local
+/// state.
+type OnProbe<'a> = &'a dyn Fn(ConfigLevel, &Path, bool);
+
+// `ConfigFinder` -------------------------------------------------------------------------------------------
+
+/// A builder to find configuration files, as an alternative to [`find_config_file`] and
+/// [`find_config_files`] with their many positional arguments.
+///
+/// [`ConfigFinder::new`] returns a builder with default settings, which can be changed by calling the
+/// builder methods. Finally, [`find_one`] or [`find_all`] searches for configuration files.
+///
+/// # Examples
+///
+/// ```
+/// # fn run() -> anyhow::Result<()> {
+/// use meadows::config::ConfigFinder;
+/// use meadows::env;
+/// use meadows::process::ExecType;
+///
+/// let config_files = ConfigFinder::new(ExecType::Binary)
+///   .file_name_pattern("{}config.toml")
+///   .paths(env::get("MY_PATH"))
+///   .find_all()?;
+///
+/// for config_file in config_files {
+///   println!("{:?} | {:?}", config_file.0, config_file.1);
+/// }
+/// #   Ok(())
+/// # }
+/// # #[cfg(not(miri))]
+/// # run();
+/// ```
+///
+/// [`find_one`]: ConfigFinder::find_one
+/// [`find_all`]: ConfigFinder::find_all
+#[derive(Clone)]
+pub struct ConfigFinder<'a> {
+  exec_type: ExecType,
+  debug_target: DebugTarget,
+  excluded_levels: Vec<ConfigLevel>,
+  expand_dirs: bool,
+  file_name_patterns: Vec<String>,
+  is_debug: bool,
+  name: Option<OsString>,
+  on_probe: Option<OnProbe<'a>>,
+  paths: Option<OsString>,
+  set_env_vars: bool,
+}
+
+impl<'a> ConfigFinder<'a> {
+  /// Sets whether debug information is output, by default on `stdout`. See [`find_config_files`].
+  #[must_use]
+  pub fn debug(mut self, is_debug: bool) -> Self {
+    self.is_debug = is_debug;
+    self
+  }
+
+  /// Sets where debug information is output if [`debug`](ConfigFinder::debug) is `true`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::config::ConfigFinder;
+  /// use meadows::config::DebugTarget;
+  /// use meadows::process::ExecType;
+  ///
+  /// let finder = ConfigFinder::new(ExecType::Binary).debug(true).debug_target(DebugTarget::Tracing);
+  /// ```
+  #[must_use]
+  pub fn debug_target(mut self, debug_target: DebugTarget) -> Self {
+    self.debug_target = debug_target;
+    self
+  }
+
+  /// Sets the executable type.
+  #[must_use]
+  pub fn exec_type(mut self, exec_type: ExecType) -> Self {
+    self.exec_type = exec_type;
+    self
+  }
+
+  /// Sets the configuration levels to exclude from the search, replacing any levels set before.
+  ///
+  /// The paths of an excluded level are not probed at all, and they are not output as debug information
+  /// either. This allows a program to never read configuration files from untrusted locations, e.g. from
+  /// the current working directory and its parents at level [`Instance`].
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::config::ConfigFinder;
+  /// use meadows::config::ConfigLevel;
+  /// use meadows::process::ExecType;
+  ///
+  /// let finder = ConfigFinder::new(ExecType::Binary).exclude_levels(&[ConfigLevel::Instance]);
+  /// ```
+  ///
+  /// [`Instance`]: ConfigLevel::Instance
+  #[must_use]
+  pub fn exclude_levels(mut self, levels: &[ConfigLevel]) -> Self {
+    self.excluded_levels = levels.to_vec();
+    self
+  }
+
+  /// Sets whether directories from `paths` are used as drop-in directories, e.g. `conf.d`.
+  ///
+  /// If `true`, each directory from `paths` is listed after its paths `${path}/.${name}.config.toml` and
+  /// `${path}/.${name}/config.toml` have been added, and every file in it whose extension equals that of a
+  /// file-name pattern is added at level [`Path`] as well. For `"{}config.toml"`, these are all `*.toml`
+  /// files. If a file-name pattern has no extension, all files are added. Subdirectories are not listed.
+  ///
+  /// The files of a directory are sorted by name, so an operator can split the configuration across
+  /// numbered files such as `10-base.toml` and `20-local.toml`. As usual, the files are ordered from
+  /// highest to lowest priority, i.e. a file whose name sorts first takes precedence. They are followed by
+  /// the paths of the next path from `paths`. Files already found, e.g. `${path}/.${name}.config.toml`, are
+  /// dropped by canonical path.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::config::ConfigFinder;
+  /// use meadows::process::ExecType;
+  ///
+  /// let finder = ConfigFinder::new(ExecType::Binary).paths(Some("/etc/my-app/conf.d")).expand_dirs(true);
+  /// ```
+  ///
+  /// [`Path`]: ConfigLevel::Path
+  #[must_use]
+  pub fn expand_dirs(mut self, expand_dirs: bool) -> Self {
+    self.expand_dirs = expand_dirs;
+    self
+  }
+
+  /// Sets a single file-name pattern, replacing any patterns set before.
+  #[must_use]
+  pub fn file_name_pattern(mut self, file_name_pattern: &str) -> Self {
+    self.file_name_patterns = vec![file_name_pattern.to_owned()];
+    self
+  }
+
+  /// Sets multiple file-name patterns, replacing any patterns set before. See [`find_config_files_multi`].
+  #[must_use]
+  pub fn file_name_patterns(mut self, file_name_patterns: &[&str]) -> Self {
+    self.file_name_patterns = file_name_patterns.iter().map(|&val| val.to_owned()).collect();
+    self
+  }
+
+  /// Finds one or more configuration files, ordered from highest to lowest priority.
+  ///
+  /// This behaves like [`find_config_files_multi`], called with the builder's settings.
+  ///
+  /// # Errors
+  ///
+  /// See [`find_config_files_multi`].
+  pub fn find_all(&self) -> Result<impl IntoIterator<Item = (ConfigLevel, PathBuf)> + use<>, FindError> {
+    self.find(false)
+  }
+
+  /// Finds the files that match `glob` in the directories specific to the name, ordered from highest to
+  /// lowest priority.
+  ///
+  /// This behaves like [`find_config_glob`], called with the builder's settings. The file-name patterns and
+  /// [`expand_dirs`] are not used. The function set with [`on_probe`] is called for each directory instead
+  /// of each file, with whether the directory can be listed.
+  ///
+  /// # Errors
+  ///
+  /// See [`find_config_glob`].
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # fn run() -> anyhow::Result<()> {
+  /// use meadows::config::ConfigFinder;
+  /// use meadows::config::ConfigLevel;
+  /// use meadows::process::ExecType;
+  ///
+  /// let plugin_files = ConfigFinder::new(ExecType::Binary)
+  ///   .exclude_levels(&[ConfigLevel::Instance])
+  ///   .find_glob("*.plugin.toml")?;
+  /// #   Ok(())
+  /// # }
+  /// # #[cfg(not(miri))]
+  /// # run();
+  /// ```
+  ///
+  /// [`expand_dirs`]: ConfigFinder::expand_dirs
+  /// [`on_probe`]: ConfigFinder::on_probe
+  pub fn find_glob(&self, glob: &str) -> Result<Vec<(ConfigLevel, PathBuf)>, FindError> {
+    use ConfigLevel::*;
+
+    if glob.contains("{}") || glob.contains(['/', std::path::MAIN_SEPARATOR]) {
+      return Err(FindError::InvalidFileNamePattern(glob.to_owned()));
+    }
+
+    // With `glob` as the bare file name, the candidates include `${dir}/${glob}` for each directory specific
+    // to the name, e.g. `${config_dir}/${name}/${glob}`
+    let finder = self.clone().file_name_pattern(&format!("{{}}{glob}")).expand_dirs(false);
+    let dirs: Vec<_> = finder
+      .candidates()?
+      .into_iter()
+      .filter(|(level, path)| {
+        !matches!(level, Package | Executable) && path.file_name() == Some(OsStr::new(glob))
+      })
+      .filter_map(|(level, path)| path.parent().map(|dir| (level, dir.to_owned())))
+      .collect();
+
+    // Collect matching files, with no canonical duplicates. Directories that cannot be listed are skipped
+
+    let mut sink = DebugSink::new(self.debug_sink_target());
+    let mut canonical_paths = HashSet::new();
+    let mut files = Vec::new();
+    for (level, dir) in &dirs {
+      let level_str = format!("{level:?}");
+      let entries = fs::read_dir(dir);
+      let bullet = if entries.is_ok() { "*" } else { "" };
+      debug!(&mut sink, "{level_str:<10} | {bullet:<1} {dir:?}")?;
+      if let Some(on_probe) = self.on_probe {
+        on_probe(*level, dir, entries.is_ok());
+      }
+      let Ok(entries) = entries else {
+        continue;
+      };
+
+      let mut dir_files: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+          path.file_name().is_some_and(|val| matches_glob(glob, &val.to_string_lossy())) && path.is_file()
+        })
+        .collect();
+      dir_files.sort();
+
+      for path in dir_files {
+        if dunce::canonicalize(&path).is_ok_and(|val| canonical_paths.insert(val)) {
+          debug!(&mut sink, "{level_str:<10} |   * {path:?}")?;
+          files.push((*level, path));
+        }
+      }
+    }
+
+    if files.is_empty() {
+      let probed = dirs.into_iter().map(|(_, dir)| dir).collect();
+      Err(FindError::FileNotFound { probed })
+    } else {
+      Ok(files)
+    }
+  }
+
+  /// Lazily finds one or more configuration files, ordered from highest to lowest priority.
+  ///
+  /// This behaves like [`find_config_files_lazy`], called with the builder's settings. The function set with
+  /// [`on_probe`] is called for each path as the returned [`Iterator`] probes it.
+  ///
+  /// # Errors
+  ///
+  /// See [`find_config_files_lazy`].
+  ///
+  /// [`on_probe`]: ConfigFinder::on_probe
+  pub fn find_lazy(&self) -> Result<impl Iterator<Item = (ConfigLevel, PathBuf)> + use<'a>, FindError> {
+    Ok(probe_config_files(self.candidates()?, self.debug_sink_target(), self.on_probe))
+  }
+
+  /// Finds a single configuration file, the one with the highest priority.
+  ///
+  /// This behaves like [`find_config_file`], called with the builder's settings.
+  ///
+  /// # Errors
+  ///
+  /// See [`find_config_file`].
+  #[allow(clippy::missing_panics_doc)]
+  pub fn find_one(&self) -> Result<(ConfigLevel, PathBuf), FindError> {
+    // If no error occurred, there must be at least one file, so `unwrap` is safe
+    Ok(self.find(true)?.into_iter().next().unwrap())
+  }
+
+  /// Sets the name to search configuration files with.
+  #[must_use]
+  pub fn name<S: AsRef<OsStr>>(mut self, name: S) -> Self {
+    self.name = Some(name.as_ref().to_owned());
+    self
+  }
+
+  /// Returns a new [`ConfigFinder`] with default settings for `exec_type`.
+  ///
+  /// | Setting             | Default Value
+  /// | :------------------ | :------------
+  /// | `debug`             | `false`
+  /// | `debug_target`      | [`DebugTarget::Stdout`]
+  /// | `exclude_levels`    | Empty, i.e. all levels are searched
+  /// | `expand_dirs`       | `false`
+  /// | `file_name_pattern` | `"{}config.toml"`
+  /// | `name`              | [`inv_name`] for [`Binary`], [`name`] for [`Example`], [`test_name`] otherwise
+  /// | `paths`             | [`None`]
+  /// | `set_env_vars`      | `true`
+  ///
+  /// [`inv_name`]: crate::env::inv_name
+  /// [`name`]: crate::env::name
+  /// [`test_name`]: crate::env::test_name
+  ///
+  /// [`Binary`]: ExecType::Binary
+  /// [`Example`]: ExecType::Example
+  #[must_use]
+  pub fn new(exec_type: ExecType) -> Self {
+    Self {
+      exec_type,
+      debug_target: DebugTarget::default(),
+      excluded_levels: Vec::new(),
+      expand_dirs: false,
+      file_name_patterns: vec![String::from("{}config.toml")],
+      is_debug: false,
+      name: None,
+      on_probe: None,
+      paths: None,
+      set_env_vars: true,
+    }
+  }
+
+  /// Sets a function that is called for each path probed, with the path's configuration level, the path, and
+  /// whether the path points to an existing file.
+  ///
+  /// Unlike debug information, which is output as text, this allows a program to observe the search,
+  /// e.g. to record it in its own `tracing` spans. The function is called in the same order as debug
+  /// information is output: From highest to lowest priority, and if a single file is searched for with
+  /// [`find_one`], only up to the first existing file, unless [`debug`] is `true`.
+  ///
+  /// The function is an [`Fn`], not an [`FnMut`], as it is shared by all clones of the [`ConfigFinder`]. To
+  /// mutate state from within the function, e.g. to collect the probed paths, wrap the state in a type with
+  /// interior mutability such as [`RefCell`](std::cell::RefCell):
+  ///
+  /// ```
+  /// # fn run() -> anyhow::Result<()> {
+  /// use std::cell::RefCell;
+  /// use std::path::Path;
+  ///
+  /// use meadows::config::ConfigFinder;
+  /// use meadows::config::ConfigLevel;
+  /// use meadows::process::ExecType;
+  ///
+  /// let probed = RefCell::new(Vec::new());
+  /// let on_probe = |_: ConfigLevel, path: &Path, _: bool| probed.borrow_mut().push(path.to_owned());
+  /// let config_file = ConfigFinder::new(ExecType::Binary).on_probe(&on_probe).find_one();
+  /// println!("Probed {:?}", probed.borrow());
+  /// #   Ok(())
+  /// # }
+  /// # #[cfg(not(miri))]
+  /// # run();
+  /// ```
+  ///
+  /// [`debug`]: ConfigFinder::debug
+  /// [`find_one`]: ConfigFinder::find_one
+  #[must_use]
+  pub fn on_probe(mut self, on_probe: OnProbe<'a>) -> Self {
+    self.on_probe = Some(on_probe);
+    self
+  }
+
+  /// Sets one or more paths, separated by the system-dependent path separator. Each path may point to a file
+  /// or directory.
+  #[must_use]
+  pub fn paths<S: AsRef<OsStr>>(mut self, paths: Option<S>) -> Self {
+    self.paths = paths.map(|val| val.as_ref().to_owned());
+    self
+  }
+
+  /// Sets whether a few environment variables are defined. See [`find_config_files`].
+  #[must_use]
+  pub fn set_env_vars(mut self, set_env_vars: bool) -> Self {
+    self.set_env_vars = set_env_vars;
+    self
+  }
+
+  /// Returns the paths to probe, ordered from highest to lowest priority. Apart from the paths at level
+  /// [`ConfigLevel::Path`], which need to be checked for being files and may be listed, see
+  /// [`ConfigFinder::expand_dirs`], this does not access the file system.
+  fn candidates(&self) -> Result<Vec<(ConfigLevel, PathBuf)>, FindError> {
+    use ConfigLevel::*;
+    use ExecType::*;
+
+    let exec_type = self.exec_type;
+    let is_enabled = |level: ConfigLevel| !self.excluded_levels.contains(&level);
+
+    let mut sink = DebugSink::new(self.debug_sink_target());
+    start_search(&mut sink, exec_type, self.set_env_vars)?;
+
+    // Define a few names and relative paths for each pattern
+
+    let name = self.name.as_ref().unwrap_or_else(|| default_name(exec_type)).to_string_lossy();
+    let file_names = self
+      .file_name_patterns
+      .iter()
+      .map(|file_name_pattern| FileNames::new(file_name_pattern, &name))
+      .collect::<Result<Vec<_>, _>>()?;
+    if file_names.is_empty() {
+      return Err(FindError::InvalidFileNamePattern(String::new()));
+    }
+
+    // Collect paths to probe, ordered from highest to lowest priority
+
+    let mut file_paths = Vec::new();
+
+    // The path is added for each pattern, in order, with `$names` bound to the pattern's `FileNames`
+    macro_rules! add {
+      ($level:expr, $names:ident => $path:expr) => {{
+        for $names in &file_names {
+          add!($level, $path);
+        }
+      }};
+      ($level:expr, $path:expr) => {{
+        file_paths.push(($level, $path));
+      }};
+    }
+
+    // Level `Path`
+    if is_enabled(Path) && let Some(paths) = &self.paths {
+      for path in std::env::split_paths(paths) {
+        let path = crate::env::expand_tilde(&expand_env_vars(path)?);
+        if path.is_file() {
+          add!(Path, path);
+        } else {
+          add!(Path, n => path.join(&n.hidden_file_name));
+          add!(Path, n => path.join(&n.hidden_relative_file));
+          if self.expand_dirs && path.is_dir() {
+            for file in list_dir_files(&path, &file_names)? {
+              add!(Path, file);
+            }
+          }
+        }
+      }
+    }
+
+    // Level `Instance`
+    if matches!(exec_type, Binary | Example) && is_enabled(Instance) {
+      let mut dir = std::env::current_dir().ok();
+      while let Some(val) = dir {
+        add!(Instance, n => val.join(&n.hidden_file_name));
+        add!(Instance, n => val.join(&n.hidden_relative_file));
+        dir = val.parent().map(PathBuf::from);
+      }
+    }
+
+    // Level `Package`
+    let manifest_dir = crate::env::get("CARGO_MANIFEST_DIR").map(PathBuf::from);
+    if is_enabled(Package) && let Some(dir) = manifest_dir {
+      match exec_type {
+        Binary => {
+          add!(Package, n => dir.join("src").join(&n.file_name));
+          add!(Package, n => dir.join("src").join("bin").join(&n.file_name));
+        }
+        Example => {
+          add!(Package, n => dir.join("examples").join(&n.file_name));
+          add!(Package, n => dir.join("examples").join(&n.bare_file_name));
+        }
+        DocTest | UnitTest => {
+          add!(Package, n => dir.join("src").join(&n.file_name));
+          add!(Package, n => dir.join("src").join(&n.bare_file_name));
+        }
+        IntegTest => {
+          add!(Package, n => dir.join("tests").join(&n.file_name));
+          add!(Package, n => dir.join("tests").join(&n.bare_file_name));
+        }
+        BenchTest => {
+          add!(Package, n => dir.join("benches").join(&n.file_name));
+          add!(Package, n => dir.join("benches").join(&n.bare_file_name));
+        }
+      }
+    }
+
+    // Level `Local`
+    if exec_type == Binary && is_enabled(Local) {
+      if let Some(dir) = dirs::home_dir() {
+        add!(Local, n => dir.join(&n.hidden_file_name));
+        add!(Local, n => dir.join(&n.hidden_relative_file));
+      }
+      if let Some(dir) = dirs::config_local_dir() {
+        add!(Local, n => dir.join(&n.relative_file));
+      }
+    }
+
+    // Level `User`
+    if exec_type == Binary && is_enabled(User) && let Some(dir) = dirs::config_dir() {
+      add!(User, n => dir.join(&n.relative_file));
+    }
+
+    // Level `System`
+    if exec_type == Binary && is_enabled(System) {
+      for dir in crate::env::system_config_dirs() {
+        add!(System, n => dir.join(&n.file_name));
+        add!(System, n => dir.join(&n.relative_file));
+      }
+    }
+
+    // Level `Executable`
+    if exec_type == Binary && is_enabled(Executable) {
+      add!(Executable, n => crate::env::inv_dir().join(&n.file_name));
+    }
+
+    Ok(file_paths)
+  }
+
+  /// Returns the target of debug information, or [`None`] if debug mode is disabled.
+  fn debug_sink_target(&self) -> Option<DebugTarget> { self.is_debug.then_some(self.debug_target) }
+
+  fn find(
+    &self,
+    find_one: bool,
+  ) -> Result<impl IntoIterator<Item = (ConfigLevel, PathBuf)> + use<>, FindError> {
+    let candidates = self.candidates()?;
+    let files = probe_config_files(candidates.clone(), self.debug_sink_target(), self.on_probe);
+
+    // In debug mode, we don't return quickly, so all paths are listed
+    let files: Vec<_> =
+      if find_one && !self.is_debug { files.take(1).collect() } else { files.collect() };
+    if files.is_empty() {
+      let probed = candidates.into_iter().map(|(_, path)| path).collect();
+      Err(FindError::FileNotFound { probed })
+    } else {
+      Ok(files.into_iter())
+    }
+  }
+}
+
+impl Debug for ConfigFinder<'_> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.debug_struct("ConfigFinder")
+      .field("debug_target", &self.debug_target)
+      .field("exec_type", &self.exec_type)
+      .field("excluded_levels", &self.excluded_levels)
+      .field("expand_dirs", &self.expand_dirs)
+      .field("file_name_patterns", &self.file_name_patterns)
+      .field("is_debug", &self.is_debug)
+      .field("name", &self.name)
+      .field("on_probe", &self.on_probe.map(|_| "..."))
+      .field("paths", &self.paths)
+      .field("set_env_vars", &self.set_env_vars)
+      .finish()
+  }
+}
+
 // `ConfigLevel` --------------------------------------------------------------------------------------------
 
 /// Configuration levels, ordered from lowest (most general) to highest (most specific) priority.
@@ -45,8 +613,9 @@ pub enum ConfigLevel {
   Executable,
   /// System-level configuration.
   ///
-  /// Configuration files at system level reside relative to a system-dependent directory. On Unix systems,
-  /// this is `/etc`. On Windows, this is `%PROGRAMDATA%`, e.g. `C:\ProgramData`.
+  /// Configuration files at system level reside relative to system-dependent directories. On Unix systems,
+  /// these are the directories from `XDG_CONFIG_DIRS`, or `/etc`. On Windows, this is `%PROGRAMDATA%`, e.g.
+  /// `C:\ProgramData`.
   System,
   /// User-level configuration.
   ///
@@ -76,6 +645,43 @@ pub enum ConfigLevel {
   Path,
 }
 
+// `DebugSink` ----------------------------------------------------------------------------------------------
+
+/// The destination of debug information, see [`DebugTarget`].
+enum DebugSink {
+  Off,
+  Stdout(AutoStreamStdoutLock),
+  Tracing,
+}
+
+impl DebugSink {
+  /// Returns a new [`DebugSink`] for `target`. If `target` is [`None`], debug information is discarded.
+  fn new(target: Option<DebugTarget>) -> Self {
+    match target {
+      None => DebugSink::Off,
+      Some(DebugTarget::Stdout) => DebugSink::Stdout(crate::io::stdout().lock()),
+      Some(DebugTarget::Tracing) => DebugSink::Tracing,
+    }
+  }
+}
+
+// `DebugTarget` --------------------------------------------------------------------------------------------
+
+/// Where debug information about the search for configuration files is output, see
+/// [`ConfigFinder::debug_target`].
+///
+/// The lines of debug information are the same for each target.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum DebugTarget {
+  /// Debug information is written to `stdout`, each line prefixed with `[meadows::config]`. This is
+  /// suitable for command-line tools.
+  #[default]
+  Stdout,
+  /// Debug information is emitted as `tracing` events at level `DEBUG` with target `meadows::config`, so
+  /// it flows through the configured subscriber. This is suitable for services.
+  Tracing,
+}
+
 // `FindError` ----------------------------------------------------------------------------------------------
 
 /// Error type for the `find` functions.
@@ -83,23 +689,198 @@ pub enum ConfigLevel {
 pub enum FindError {
   /// File not found.
   #[error("File not found")]
-  FileNotFound,
+  FileNotFound {
+    /// The paths that were probed, ordered from highest to lowest priority.
+    probed: Vec<PathBuf>,
+  },
   /// Invalid file-name pattern.
   #[error("Invalid file-name pattern `{0}`")]
   InvalidFileNamePattern(String),
   /// [`io::Error`].
   #[error("I/O error")]
   Io(#[from] io::Error),
+  /// An environment variable referenced in a path is not defined.
+  #[error("Environment variable `{0}` is not defined")]
+  UndefinedEnvVar(String),
+  /// A TOML file cannot be parsed. The source is the error reported by the parser, i.e.
+  /// `toml::de::Error` if the `toml` feature is enabled.
+  #[error("Cannot parse TOML file {0:?}")]
+  Toml(PathBuf, #[source] Box<dyn std::error::Error + Send + Sync>),
 }
 
 impl FindError {
   /// Checks if the error should be printed.
   #[must_use]
-  pub fn should_print(&self) -> bool { !matches!(self, Self::FileNotFound) }
+  pub fn should_print(&self) -> bool { !matches!(self, Self::FileNotFound { .. }) }
+}
+
+// `FileNames` ----------------------------------------------------------------------------------------------
+
+/// File names and relative paths derived from a file-name pattern such as `"{}config.toml"`.
+struct FileNames {
+  /// `config.toml`
+  bare_file_name: String,
+  /// `{name}.config.toml`
+  file_name: String,
+  /// `.{name}.config.toml`
+  hidden_file_name: String,
+  /// `{name}/config.toml`
+  relative_file: PathBuf,
+  /// `.{name}/config.toml`
+  hidden_relative_file: PathBuf,
+}
+
+impl FileNames {
+  fn new(file_name_pattern: &str, name: &str) -> Result<Self, FindError> {
+    let bare_file_name = replace_in_pattern(file_name_pattern, "")?;
+    let file_name = replace_in_pattern(file_name_pattern, name)?;
+    let hidden_file_name = format!(".{file_name}");
+    let relative_file = PathBuf::from(name).join(&bare_file_name);
+    let hidden_relative_file = PathBuf::from(format!(".{name}")).join(&bare_file_name);
+    Ok(Self { bare_file_name, file_name, hidden_file_name, relative_file, hidden_relative_file })
+  }
+}
+
+// `WatchHandle` --------------------------------------------------------------------------------------------
+
+/// A handle to a file watcher, as returned by [`watch`].
+///
+/// Dropping the handle stops the watcher. A pending, debounced change that has not been reported yet is
+/// discarded.
+#[cfg(feature = "notify")]
+pub struct WatchHandle {
+  thread: Option<thread::JoinHandle<()>>,
+  watcher: Option<notify::RecommendedWatcher>,
+}
+
+#[cfg(feature = "notify")]
+impl Debug for WatchHandle {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result { f.debug_struct("WatchHandle").finish_non_exhaustive() }
+}
+
+#[cfg(feature = "notify")]
+impl Drop for WatchHandle {
+  fn drop(&mut self) {
+    // Dropping the watcher disconnects the channel, which makes the thread exit
+    self.watcher.take();
+    if let Some(thread) = self.thread.take() {
+      let _ = thread.join();
+    }
+  }
 }
 
 // Functions ------------------------------------------------------------------------------------------------
 
+/// Returns the directory in which configuration files for `name` reside at `level`.
+///
+/// This is useful to decide where to create a new configuration file, e.g. a default configuration at
+/// [`User`] level. The directory is the one the `find` functions search at `level`, see
+/// [`find_config_files`]:
+///
+/// | Level          | Directory
+/// | :------------- | :--------
+/// | [`Path`]       | None, as the paths are specified explicitly
+/// | [`Instance`]   | `.${name}` in the current working directory
+/// | [`Package`]    | `src`, `examples`, `tests`, or `benches` in the package's manifest directory, depending on `exec_type`
+/// | [`Local`]      | `.${name}` in the home directory, or `${name}` in [`dirs::config_local_dir`]
+/// | [`User`]       | `${name}` in [`dirs::config_dir`]
+/// | [`System`]     | `${name}` in the first directory returned by [`crate::env::system_config_dirs`]
+/// | [`Executable`] | The directory of the executable, see [`crate::env::inv_dir`]
+///
+/// Returns [`None`] if `level` is not searched for `exec_type`, e.g. [`User`] for [`ExecType::UnitTest`], or
+/// if the directory cannot be determined, e.g. [`Package`] if `CARGO_MANIFEST_DIR` is not set.
+///
+/// The directory is not created, and it is not checked whether it exists. Therefore, the path is not
+/// canonicalized either: It is joined from the base directory as reported by the respective function, e.g.
+/// [`dirs::config_dir`], and may contain symbolic links. If a canonical path is needed, e.g. to compare it
+/// with another path, canonicalize it via [`dunce::canonicalize`] once the directory exists.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::config;
+/// use meadows::config::ConfigLevel;
+/// use meadows::process::ExecType;
+///
+/// if let Some(dir) = config::config_dir_for(ConfigLevel::User, ExecType::Binary, "my-app".as_ref()) {
+///   println!("Creating a default configuration in `{}`", dir.display());
+/// }
+/// ```
+///
+/// [`Path`]: ConfigLevel::Path
+/// [`Instance`]: ConfigLevel::Instance
+/// [`Package`]: ConfigLevel::Package
+/// [`Local`]: ConfigLevel::Local
+/// [`User`]: ConfigLevel::User
+/// [`System`]: ConfigLevel::System
+/// [`Executable`]: ConfigLevel::Executable
+#[must_use]
+pub fn config_dir_for(level: ConfigLevel, exec_type: ExecType, name: &OsStr) -> Option<PathBuf> {
+  use ConfigLevel::*;
+  use ExecType::*;
+
+  let hidden_name = || {
+    let mut ret = OsString::from(".");
+    ret.push(name);
+    ret
+  };
+
+  match level {
+    Path => None,
+    Instance => {
+      if !matches!(exec_type, Binary | Example) {
+        return None;
+      }
+      std::env::current_dir().ok().map(|dir| dir.join(hidden_name()))
+    }
+    Package => {
+      let dir = crate::env::get("CARGO_MANIFEST_DIR").map(PathBuf::from)?;
+      let sub_dir = match exec_type {
+        Binary | DocTest | UnitTest => "src",
+        Example => "examples",
+        IntegTest => "tests",
+        BenchTest => "benches",
+      };
+      Some(dir.join(sub_dir))
+    }
+    _ if exec_type != Binary => None,
+    Local => dirs::home_dir()
+      .map(|dir| dir.join(hidden_name()))
+      .or_else(|| dirs::config_local_dir().map(|dir| dir.join(name))),
+    User => dirs::config_dir().map(|dir| dir.join(name)),
+    System => crate::env::system_config_dirs().into_iter().next().map(|dir| dir.join(name)),
+    Executable => Some(crate::env::inv_dir().clone()),
+  }
+}
+
+/// Returns a [`ConfigFinder`] for the arguments of the `find` functions.
+fn config_finder<Paths: AsRef<OsStr>>(
+  exec_type: ExecType,
+  file_name_patterns: &[&str],
+  is_debug: bool,
+  name: &OsStr,
+  paths: Option<&Paths>,
+  set_env_vars: bool,
+) -> ConfigFinder<'static> {
+  ConfigFinder::new(exec_type)
+    .file_name_patterns(file_name_patterns)
+    .debug(is_debug)
+    .name(name)
+    .paths(paths)
+    .set_env_vars(set_env_vars)
+}
+
+/// Returns the default name to search configuration files with for `exec_type`.
+pub(crate) fn default_name(exec_type: ExecType) -> &'static OsString {
+  use ExecType::*;
+
+  match exec_type {
+    Binary => crate::env::inv_name(),
+    Example => crate::env::name(),
+    DocTest | UnitTest | IntegTest | BenchTest => crate::env::test_name(),
+  }
+}
+
 /// Finds a configuration file.
 ///
 /// Unlike [`find_config_files`], the function looks for a single configuration file only. If an existing
@@ -142,10 +923,105 @@ pub fn find_config_file<Paths: AsRef<OsStr>>(
   paths: Option<&Paths>,
   set_env_vars: bool,
 ) -> Result<(ConfigLevel, PathBuf), FindError> {
-  let files =
-    find_config_files_impl(true, exec_type, file_name_pattern, is_debug, name, paths, set_env_vars)?;
-  // If no error occurred, there must be at least one file, so `unwrap` is safe
-  Ok(files.into_iter().next().unwrap())
+  config_finder(exec_type, &[file_name_pattern], is_debug, name, paths, set_env_vars).find_one()
+}
+
+/// Finds all configuration files matching a glob, e.g. to find the configuration files of plugins, ordered
+/// from highest to lowest priority.
+///
+/// Unlike [`find_config_files`], which probes for files with known names, the function lists the
+/// configuration directory of each applicable level and collects every file whose name matches `glob`.
+/// Within a directory, files are sorted by name. Files are deduplicated by canonical path.
+///
+/// # Glob Syntax
+///
+/// `glob` is matched against file names only:
+///
+/// | Pattern | Matches
+/// | :------ | :------
+/// | `*`     | Any sequence of characters, including the empty sequence and a leading `.`
+/// | `?`     | Any single character
+/// | Other   | The character itself, case-sensitively
+///
+/// `glob` must not contain a path separator. Since the directories searched are specific to `name`, `glob`
+/// must not contain `"{}"` either: `{}` patterns and globs are mutually exclusive.
+///
+/// # Directory Search
+///
+/// The function lists the following directories, from highest to lowest priority, in the exact order shown,
+/// if they exist. See [`find_config_files`] for the placeholders used:
+///
+/// | Configuration Level | `exec_type`             | Directory
+/// | :------------------ | :---------------------- | :--------
+/// | [`Path`]            | Any                     | `${path}/.${name}`
+/// | [`Instance`]        | [`Binary`], [`Example`] | `/home/alice/.${name}`
+/// | [`Instance`]        | [`Binary`], [`Example`] | `/home/.${name}`
+/// | [`Instance`]        | [`Binary`], [`Example`] | `/.${name}`
+/// | [`Local`]           | [`Binary`]              | `${home_dir}/.${name}`
+/// | [`Local`]           | [`Binary`]              | `${config_local_dir}/${name}`
+/// | [`User`]            | [`Binary`]              | `${config_dir}/${name}`
+/// | [`System`]          | [`Binary`]              | `${system_config_dir}/${name}`
+///
+/// Levels [`Package`] and [`Executable`] have no directories specific to `name` and are not searched.
+/// Directories that do not exist or cannot be listed are skipped.
+///
+/// If `is_debug` is `true`, the function outputs the directories searched and the files found on `stdout`.
+///
+/// # Errors
+///
+/// Returns [`Err`] with
+///
+/// - [`FindError::FileNotFound`] if no file matches `glob`. The error contains the directories searched
+/// - [`FindError::InvalidFileNamePattern`] if `glob` contains `"{}"` or a path separator
+/// - [`FindError::Io`] if an [`io::Error`] occurs
+/// - [`FindError::UndefinedEnvVar`] if a path from `paths` references an undefined environment variable
+///
+/// # Examples
+///
+/// ```
+/// # fn run() -> anyhow::Result<()> {
+/// use meadows::config;
+/// use meadows::env;
+/// use meadows::process::ExecType;
+///
+/// let my_path = env::get("MY_PATH");
+/// let plugin_files = config::find_config_glob(
+///   ExecType::Binary, // `exec_type`
+///   "*.plugin.toml",  // `glob`
+///   false,            // `is_debug`
+///   env::inv_name(),  // `name`
+///   my_path.as_ref(), // `paths`
+///   true,             // `set_env_vars`
+/// )?;
+///
+/// for plugin_file in plugin_files {
+///   println!("{:?} | {:?}", plugin_file.0, plugin_file.1);
+/// }
+/// #   Ok(())
+/// # }
+/// # #[cfg(not(miri))]
+/// # run();
+/// ```
+///
+/// [`Path`]: ConfigLevel::Path
+/// [`Instance`]: ConfigLevel::Instance
+/// [`Package`]: ConfigLevel::Package
+/// [`Local`]: ConfigLevel::Local
+/// [`User`]: ConfigLevel::User
+/// [`System`]: ConfigLevel::System
+/// [`Executable`]: ConfigLevel::Executable
+///
+/// [`Binary`]: ExecType::Binary
+/// [`Example`]: ExecType::Example
+pub fn find_config_glob<Paths: AsRef<OsStr>>(
+  exec_type: ExecType,
+  glob: &str,
+  is_debug: bool,
+  name: &OsStr,
+  paths: Option<&Paths>,
+  set_env_vars: bool,
+) -> Result<Vec<(ConfigLevel, PathBuf)>, FindError> {
+  config_finder(exec_type, &[], is_debug, name, paths, set_env_vars).find_glob(glob)
 }
 
 /// Finds one or more configuration files suitable for a given `exec_type`, ordered from highest to lowest
@@ -189,8 +1065,8 @@ pub fn find_config_file<Paths: AsRef<OsStr>>(
 /// | `${inv_dir}`           | The invocation directory as returned by [`inv_dir`]
 /// | `${manifest_dir}`      | The package's manifest directory. This applies only if the executable is run via Cargo
 /// | `${name}`              | `name`
-/// | `${path}`              | Each path from `paths`, which is separated by the system-dependent path separator. Each path may point to a file or directory. This applies only if `paths` is a [`Some`]
-/// | `${system_config_dir}` | A system-dependent directory as returned by [`system_config_dir`]
+/// | `${path}`              | Each path from `paths`, which is separated by the system-dependent path separator. Each path may point to a file or directory. This applies only if `paths` is a [`Some`]. See [Paths](#paths)
+/// | `${system_config_dir}` | Each system-dependent directory as returned by [`system_config_dirs`], in order. On Unix, these are the directories from `XDG_CONFIG_DIRS`, falling back to `/etc`. On Windows, this is `%PROGRAMDATA%`
 ///
 /// The function probes the following paths, from highest to lowest priority, in the exact order shown, if
 /// they point to existing files:
@@ -200,12 +1076,12 @@ pub fn find_config_file<Paths: AsRef<OsStr>>(
 /// | [`Path`]            | Any                       | `${path}`
 /// | [`Path`]            | Any                       | `${path}/.${name}.config.toml`
 /// | [`Path`]            | Any                       | `${path}/.${name}/config.toml`
-/// | [`Instance`]        | [`Binary`]                | `/home/alice/.${name}.config.toml`
-/// | [`Instance`]        | [`Binary`]                | `/home/alice/.${name}/config.toml`
-/// | [`Instance`]        | [`Binary`]                | `/home/.${name}.config.toml`
-/// | [`Instance`]        | [`Binary`]                | `/home/.${name}/config.toml`
-/// | [`Instance`]        | [`Binary`]                | `/.${name}.config.toml`
-/// | [`Instance`]        | [`Binary`]                | `/.${name}/config.toml`
+/// | [`Instance`]        | [`Binary`], [`Example`]   | `/home/alice/.${name}.config.toml`
+/// | [`Instance`]        | [`Binary`], [`Example`]   | `/home/alice/.${name}/config.toml`
+/// | [`Instance`]        | [`Binary`], [`Example`]   | `/home/.${name}.config.toml`
+/// | [`Instance`]        | [`Binary`], [`Example`]   | `/home/.${name}/config.toml`
+/// | [`Instance`]        | [`Binary`], [`Example`]   | `/.${name}.config.toml`
+/// | [`Instance`]        | [`Binary`], [`Example`]   | `/.${name}/config.toml`
 /// | [`Package`]         | [`Binary`]                | `${manifest_dir}/src/${name}.config.toml`
 /// | [`Package`]         | [`Binary`]                | `${manifest_dir}/src/bin/${name}.config.toml`
 /// | [`Package`]         | [`Example`]               | `${manifest_dir}/examples/${name}.config.toml`
@@ -224,6 +1100,20 @@ pub fn find_config_file<Paths: AsRef<OsStr>>(
 /// | [`System`]          | [`Binary`]                | `${system_config_dir}/${name}/config.toml`
 /// | [`Executable`]      | [`Binary`]                | `${inv_dir}/${name}.config.toml`
 ///
+/// If there are multiple system-dependent directories, both [`System`] paths are probed for each directory
+/// before the next directory is probed.
+///
+/// # Paths
+///
+/// Each path from `paths` may reference environment variables, e.g. as `${NAME}`, which are expanded before
+/// the path is probed, see [`try_expand`](crate::env::try_expand) for the syntax. This includes the
+/// variables defined if `set_env_vars` is `true`, so e.g. `${home_dir}/cfg` resolves to the `cfg` directory
+/// in the user's home directory. If a referenced variable is not defined, [`FindError::UndefinedEnvVar`] is
+/// returned.
+///
+/// After that, a leading `~` is replaced by the user's home directory, see
+/// [`expand_tilde`](crate::env::expand_tilde).
+///
 /// The function returns an [`IntoIterator`] that produces pairs of [`ConfigLevel`]s and [`PathBuf`]s for
 /// existing files. How multiple configuration files are combined into a specific configuration, is left
 /// entirely to the program. The general idea is that settings from a configuration file override settings
@@ -233,9 +1123,156 @@ pub fn find_config_file<Paths: AsRef<OsStr>>(
 ///
 /// Returns [`Err`] with
 ///
-/// - [`FindError::FileNotFound`] if a configuration file cannot be found
-/// - [`FindError::InvalidFileNamePattern`] if `file_name_pattern` does not contain `"{}"`
-/// - [`FindError::Io`] if an [`io::Error`] occurs
+/// - [`FindError::FileNotFound`] if a configuration file cannot be found. The error contains all probed
+///   paths, so the program can tell the user where configuration files are searched for
+/// - [`FindError::InvalidFileNamePattern`] if `file_name_pattern` does not contain `"{}"`
+/// - [`FindError::Io`] if an [`io::Error`] occurs
+/// - [`FindError::UndefinedEnvVar`] if a path from `paths` references an undefined environment variable
+///
+/// # Examples
+///
+/// ```
+/// # fn run() -> anyhow::Result<()> {
+/// use meadows::config;
+/// use meadows::env;
+/// use meadows::process::ExecType;
+///
+/// let my_path = env::get("MY_PATH");
+/// let config_files = config::find_config_files(
+///   ExecType::Binary, // `exec_type`
+///   "{}config.toml",  // `file_name_pattern`
+///   true,             // `is_debug`
+///   env::inv_name(),  // `name`
+///   my_path.as_ref(), // `paths`
+///   true              // `set_env_vars`
+/// )?;
+///
+/// for config_file in config_files {
+///   println!("{:?} | {:?}", config_file.0, config_file.1);
+/// }
+/// #   Ok(())
+/// # }
+/// # #[cfg(not(miri))]
+/// # run();
+/// ```
+///
+/// [`dir`]: crate::env::dir
+/// [`inv_dir`]: crate::env::inv_dir
+/// [`inv_name`]: crate::env::inv_name
+/// [`inv_path`]: crate::env::inv_path
+/// [`name`]: crate::env::name
+/// [`path`]: crate::env::path
+/// [`system_config_dirs`]: crate::env::system_config_dirs
+/// [`test_name`]: crate::env::test_name
+///
+/// [`Path`]: ConfigLevel::Path
+/// [`Instance`]: ConfigLevel::Instance
+/// [`Package`]: ConfigLevel::Package
+/// [`Local`]: ConfigLevel::Local
+/// [`User`]: ConfigLevel::User
+/// [`System`]: ConfigLevel::System
+/// [`Executable`]: ConfigLevel::Executable
+///
+/// [`Binary`]: ExecType::Binary
+/// [`Example`]: ExecType::Example
+/// [`DocTest`]: ExecType::DocTest
+/// [`UnitTest`]: ExecType::UnitTest
+/// [`IntegTest`]: ExecType::IntegTest
+/// [`BenchTest`]: ExecType::BenchTest
+pub fn find_config_files<Paths: AsRef<OsStr>>(
+  exec_type: ExecType,
+  file_name_pattern: &str,
+  is_debug: bool,
+  name: &OsStr,
+  paths: Option<&Paths>,
+  set_env_vars: bool,
+) -> Result<impl IntoIterator<Item = (ConfigLevel, PathBuf)>, FindError> {
+  config_finder(exec_type, &[file_name_pattern], is_debug, name, paths, set_env_vars).find_all()
+}
+
+/// Lazily finds one or more configuration files suitable for a given `exec_type`, ordered from highest to
+/// lowest priority.
+///
+/// This works like [`find_config_files`], but returns an [`Iterator`] that probes the file system on demand
+/// as it is advanced, rather than probing all paths up front. A program that only needs the first few files
+/// therefore doesn't probe the paths of lower-priority levels. Canonical duplicates are skipped as the
+/// iterator goes.
+///
+/// If `is_debug` is `true`, each path is output on `stdout` as it is probed.
+///
+/// Since files are probed on demand, an empty iterator is returned rather than
+/// [`FindError::FileNotFound`] if no configuration file exists.
+///
+/// # Errors
+///
+/// Returns [`Err`] with
+///
+/// - [`FindError::InvalidFileNamePattern`] if `file_name_pattern` does not contain `"{}"`
+/// - [`FindError::Io`] if an [`io::Error`] occurs
+/// - [`FindError::UndefinedEnvVar`] if a path from `paths` references an undefined environment variable
+///
+/// # Examples
+///
+/// ```
+/// # fn run() -> anyhow::Result<()> {
+/// use meadows::config;
+/// use meadows::env;
+/// use meadows::process::ExecType;
+///
+/// let my_path = env::get("MY_PATH");
+/// let config_files = config::find_config_files_lazy(
+///   ExecType::Binary, // `exec_type`
+///   "{}config.toml",  // `file_name_pattern`
+///   false,            // `is_debug`
+///   env::inv_name(),  // `name`
+///   my_path.as_ref(), // `paths`
+///   true,             // `set_env_vars`
+/// )?;
+///
+/// // Only probe as far as needed to find two files
+/// for config_file in config_files.take(2) {
+///   println!("{:?} | {:?}", config_file.0, config_file.1);
+/// }
+/// #   Ok(())
+/// # }
+/// # #[cfg(not(miri))]
+/// # run();
+/// ```
+pub fn find_config_files_lazy<Paths: AsRef<OsStr>>(
+  exec_type: ExecType,
+  file_name_pattern: &str,
+  is_debug: bool,
+  name: &OsStr,
+  paths: Option<&Paths>,
+  set_env_vars: bool,
+) -> Result<impl Iterator<Item = (ConfigLevel, PathBuf)>, FindError> {
+  config_finder(exec_type, &[file_name_pattern], is_debug, name, paths, set_env_vars).find_lazy()
+}
+
+/// Finds one or more configuration files suitable for a given `exec_type`, ordered from highest to lowest
+/// priority, probing multiple file-name patterns.
+///
+/// This works like [`find_config_files`], but takes a slice of file-name patterns instead of a single one,
+/// e.g. to support multiple file formats.
+///
+/// # Multiple Patterns
+///
+/// The priority order of the configuration levels is retained: All patterns are probed at one level before
+/// the next level is probed. Within a level, each path from the table in [`find_config_files`] is probed
+/// with all patterns, in the order of `file_name_patterns`, before the next path is probed.
+///
+/// As an example, let `file_name_patterns` be `["{}config.toml", "{}config.json"]`. At level [`Path`], these
+/// paths are probed for each `${path}` that points to a directory, in this order:
+///
+/// 1. `${path}/.${name}.config.toml`
+/// 2. `${path}/.${name}.config.json`
+/// 3. `${path}/.${name}/config.toml`
+/// 4. `${path}/.${name}/config.json`
+///
+/// # Errors
+///
+/// See [`find_config_files`]. Additionally, returns [`Err`] with [`FindError::InvalidFileNamePattern`] if
+/// `file_name_patterns` is empty.
 ///
 /// # Examples
 ///
@@ -246,13 +1283,13 @@ pub fn find_config_file<Paths: AsRef<OsStr>>(
 /// use meadows::process::ExecType;
 ///
 /// let my_path = env::get("MY_PATH");
-/// let config_files = config::find_config_files(
-///   ExecType::Binary, // `exec_type`
-///   "{}config.toml",  // `file_name_pattern`
-///   true,             // `is_debug`
-///   env::inv_name(),  // `name`
-///   my_path.as_ref(), // `paths`
-///   true              // `set_env_vars`
+/// let config_files = config::find_config_files_multi(
+///   ExecType::Binary,                                     // `exec_type`
+///   &["{}config.toml", "{}config.yaml", "{}config.json"], // `file_name_patterns`
+///   false,                                                // `is_debug`
+///   env::inv_name(),                                      // `name`
+///   my_path.as_ref(),                                     // `paths`
+///   true,                                                 // `set_env_vars`
 /// )?;
 ///
 /// for config_file in config_files {
@@ -264,209 +1301,263 @@ pub fn find_config_file<Paths: AsRef<OsStr>>(
 /// # run();
 /// ```
 ///
-/// [`dir`]: crate::env::dir
-/// [`inv_dir`]: crate::env::inv_dir
-/// [`inv_name`]: crate::env::inv_name
-/// [`inv_path`]: crate::env::inv_path
-/// [`name`]: crate::env::name
-/// [`path`]: crate::env::path
-/// [`system_config_dir`]: crate::env::system_config_dir
-/// [`test_name`]: crate::env::test_name
-///
 /// [`Path`]: ConfigLevel::Path
-/// [`Instance`]: ConfigLevel::Instance
-/// [`Package`]: ConfigLevel::Package
-/// [`Local`]: ConfigLevel::Local
-/// [`User`]: ConfigLevel::User
-/// [`System`]: ConfigLevel::System
-/// [`Executable`]: ConfigLevel::Executable
-///
-/// [`Binary`]: ExecType::Binary
-/// [`Example`]: ExecType::Example
-/// [`DocTest`]: ExecType::DocTest
-/// [`UnitTest`]: ExecType::UnitTest
-/// [`IntegTest`]: ExecType::IntegTest
-/// [`BenchTest`]: ExecType::BenchTest
-pub fn find_config_files<Paths: AsRef<OsStr>>(
+pub fn find_config_files_multi<Paths: AsRef<OsStr>>(
   exec_type: ExecType,
-  file_name_pattern: &str,
+  file_name_patterns: &[&str],
   is_debug: bool,
   name: &OsStr,
   paths: Option<&Paths>,
   set_env_vars: bool,
 ) -> Result<impl IntoIterator<Item = (ConfigLevel, PathBuf)>, FindError> {
-  find_config_files_impl(false, exec_type, file_name_pattern, is_debug, name, paths, set_env_vars)
+  config_finder(exec_type, file_name_patterns, is_debug, name, paths, set_env_vars).find_all()
 }
 
-fn find_config_files_impl<Paths: AsRef<OsStr>>(
-  find_one: bool,
-  exec_type: ExecType,
-  file_name_pattern: &str,
-  is_debug: bool,
-  name: &OsStr,
-  paths: Option<&Paths>,
-  set_env_vars: bool,
-) -> Result<impl IntoIterator<Item = (ConfigLevel, PathBuf)>, FindError> {
-  use ConfigLevel::*;
-  use ExecType::*;
-
-  // Some introductory debug info
-
-  let mut stdout = if is_debug { Some(crate::io::stdout().lock()) } else { None };
-  let stdout = &mut stdout;
+/// Reads TOML configuration files and merges them into a single [`toml::Value`].
+///
+/// `files` are expected to be ordered from highest to lowest priority, as returned by [`find_config_files`].
+/// The files are read in reverse order, so settings from higher-priority files override settings from
+/// lower-priority files.
+///
+/// # Merge Policy
+///
+/// Tables are merged deeply: For each key of a higher-priority table, the value is merged into the value of
+/// the same key of the lower-priority table, if both values are tables. Otherwise, the value replaces the
+/// lower-priority value. In particular, arrays are not concatenated, but replaced as a whole.
+///
+/// # Errors
+///
+/// Returns [`Err`] with
+///
+/// - [`FindError::Io`] if a file cannot be read
+/// - [`FindError::Toml`] if a file cannot be parsed
+///
+/// # Examples
+///
+/// ```
+/// # fn run() -> anyhow::Result<()> {
+/// use meadows::config;
+/// use meadows::env;
+/// use meadows::process::ExecType;
+///
+/// let my_path = env::get("MY_PATH");
+/// let config_files = config::find_config_files(
+///   ExecType::Binary, // `exec_type`
+///   "{}config.toml",  // `file_name_pattern`
+///   false,            // `is_debug`
+///   env::inv_name(),  // `name`
+///   my_path.as_ref(), // `paths`
+///   true,             // `set_env_vars`
+/// )?;
+/// let config = config::merge_toml(config_files)?;
+/// println!("{config}");
+/// #   Ok(())
+/// # }
+/// # #[cfg(not(miri))]
+/// # run();
+/// ```
+#[cfg(feature = "toml")]
+pub fn merge_toml<I>(files: I) -> Result<toml::Value, FindError>
+where
+  I: IntoIterator<Item = (ConfigLevel, PathBuf)>, {
+  let files: Vec<_> = files.into_iter().collect();
+  let mut ret = toml::Value::Table(toml::Table::new());
+  for (_, path) in files.into_iter().rev() {
+    let text = fs::read_to_string(&path)?;
+    let table: toml::Table = toml::from_str(&text).map_err(|err| FindError::Toml(path, Box::new(err)))?;
+    merge_toml_value(&mut ret, toml::Value::Table(table));
+  }
+  Ok(ret)
+}
 
-  debug!(stdout, "Checking paths for {} executable", match exec_type {
-    Binary => "binary",
-    Example => "example",
-    DocTest => "doc-test",
-    UnitTest => "unit-test",
-    IntegTest => "integration-test",
-    BenchTest => "benchmark-test",
-  })?;
+/// Watches a file for changes.
+///
+/// `on_change` is called with the absolute path of the file on a background thread whenever the file has
+/// been created, modified, or removed. Events are debounced: After an event, the watcher waits until no
+/// further event has occurred for [`WATCH_DEBOUNCE`], then calls `on_change` once. So an editor writing a
+/// file in several steps triggers only a single call.
+///
+/// The function watches the parent directory of `path` rather than the file itself. Hence, edits that
+/// replace the file, e.g. by writing a temporary file and renaming it to `path`, are detected as well, and
+/// `path` need not exist when the function is called. The parent directory must exist, though.
+///
+/// The watcher runs until the returned [`WatchHandle`] is dropped.
+///
+/// # Errors
+///
+/// Returns [`Err`] if the watcher cannot be created, e.g. if the parent directory of `path` does not exist.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+///
+/// use meadows::config;
+///
+/// let handle = config::watch(Path::new("config.toml"), |path| println!("{path:?} has changed"))?;
+/// // ...
+/// drop(handle); // Stops watching
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "notify")]
+pub fn watch<F>(path: &Path, on_change: F) -> io::Result<WatchHandle>
+where
+  F: Fn(&Path) + Send + 'static, {
+  use notify::Watcher;
 
-  debug!(stdout, "Current directory: {}", {
-    match std::env::current_dir() {
-      Ok(dir) => format!("{dir:?}"),
-      Err(_) => String::from("-"),
-    }
-  })?;
+  let path = std::path::absolute(path)?;
+  let (Some(dir), Some(file_name)) = (path.parent(), path.file_name()) else {
+    return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Cannot watch path {path:?}")));
+  };
+  let file_name = file_name.to_owned();
 
-  // If requested, set env vars. This is executed only once
+  let (tx, rx) = mpsc::channel();
+  let mut watcher = notify::recommended_watcher(tx).map_err(notify_to_io_error)?;
+  watcher.watch(dir, notify::RecursiveMode::NonRecursive).map_err(notify_to_io_error)?;
 
-  if set_env_vars {
-    self::set_env_vars(stdout, exec_type)?;
-  }
-
-  // Define a few names and relative paths
-
-  let name = name.to_string_lossy();
-  // `config.toml`
-  let bare_file_name = replace_in_pattern(file_name_pattern, "")?;
-  // `{name}.config.toml`
-  let file_name = replace_in_pattern(file_name_pattern, &name)?;
-  // `.{name}.config.toml`
-  let hidden_file_name = format!(".{file_name}");
-  // `{name}/config.toml`
-  let relative_file = PathBuf::from(name.as_ref()).join(&bare_file_name);
-  // `.{name}/config.toml`
-  let hidden_relative_file = PathBuf::from(format!(".{name}")).join(&bare_file_name);
-
-  // Collect paths to probe, ordered from highest to lowest priority
-
-  let mut file_paths = Vec::new();
-
-  // Closure returns a `Some` if the outer function should return quickly
-  let mut add_file_path =
-    |level: ConfigLevel, path: PathBuf| -> io::Result<Option<(ConfigLevel, PathBuf)>> {
-      file_paths.push((level, path.clone()));
-      if is_debug {
-        let level_str = format!("{level:?}");
-        let bullet = if path.is_file() { "*" } else { "" };
-        debug!(stdout, "{level_str:<10} | {bullet:<1} {path:?}")?;
-        // In debug mode, we don't return quickly
-        Ok(None)
-      } else if find_one && path.is_file() {
-        Ok(Some((level, path)))
-      } else {
-        Ok(None)
-      }
+  let thread = thread::spawn(move || {
+    let is_relevant = |event: &notify::Result<notify::Event>| {
+      event.as_ref().is_ok_and(|event| {
+        !event.kind.is_access() && event.paths.iter().any(|path| path.file_name() == Some(&file_name))
+      })
     };
 
-  // Macro returns from outer function if closure returns a `Some`
-  macro_rules! add {
-    ($level:expr, $path:expr) => {{
-      if let Some(val) = add_file_path($level, $path)? {
-        return Ok(vec![val].into_iter());
+    while let Ok(event) = rx.recv() {
+      if !is_relevant(&event) {
+        continue;
       }
-    }};
-  }
-
-  // Level `Path`
-  if let Some(paths) = &paths {
-    for path in std::env::split_paths(paths) {
-      if path.is_file() {
-        add!(Path, path);
-      } else {
-        add!(Path, path.join(&hidden_file_name));
-        add!(Path, path.join(&hidden_relative_file));
+      // Wait until no relevant event has occurred for `WATCH_DEBOUNCE`
+      let mut deadline = Instant::now() + WATCH_DEBOUNCE;
+      loop {
+        match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+          Ok(event) if is_relevant(&event) => deadline = Instant::now() + WATCH_DEBOUNCE,
+          Ok(_) => {}
+          Err(mpsc::RecvTimeoutError::Timeout) => break,
+          Err(mpsc::RecvTimeoutError::Disconnected) => return,
+        }
       }
+      on_change(&path);
     }
-  }
+  });
 
-  // Level `Instance`
-  if exec_type == Binary {
-    let mut dir = std::env::current_dir().ok();
-    while let Some(val) = dir {
-      add!(Instance, val.join(&hidden_file_name));
-      add!(Instance, val.join(&hidden_relative_file));
-      dir = val.parent().map(PathBuf::from);
-    }
+  Ok(WatchHandle { thread: Some(thread), watcher: Some(watcher) })
+}
+
+/// Expands references to environment variables in `path` via [`try_expand`](crate::env::try_expand). See
+/// [`find_config_files`].
+///
+/// Paths that are not valid Unicode are returned verbatim.
+fn expand_env_vars(path: PathBuf) -> Result<PathBuf, FindError> {
+  let Some(text) = path.to_str() else {
+    return Ok(path);
+  };
+  match crate::env::try_expand(text) {
+    Ok(Cow::Borrowed(_)) => Ok(path),
+    Ok(Cow::Owned(ret)) => Ok(PathBuf::from(ret)),
+    Err(ExpandError::UndefinedVar(name)) => Err(FindError::UndefinedEnvVar(name)),
   }
+}
 
-  // Level `Package`
-  let manifest_dir = crate::env::get("CARGO_MANIFEST_DIR").map(PathBuf::from);
-  if let Some(dir) = manifest_dir {
-    match exec_type {
-      Binary => {
-        add!(Package, dir.join("src").join(&file_name));
-        add!(Package, dir.join("src").join("bin").join(&file_name));
-      }
-      Example => {
-        add!(Package, dir.join("examples").join(&file_name));
-        add!(Package, dir.join("examples").join(&bare_file_name));
-      }
-      DocTest | UnitTest => {
-        add!(Package, dir.join("src").join(&file_name));
-        add!(Package, dir.join("src").join(&bare_file_name));
-      }
-      IntegTest => {
-        add!(Package, dir.join("tests").join(&file_name));
-        add!(Package, dir.join("tests").join(&bare_file_name));
-      }
-      BenchTest => {
-        add!(Package, dir.join("benches").join(&file_name));
-        add!(Package, dir.join("benches").join(&bare_file_name));
-      }
-    }
+#[cfg(feature = "notify")]
+fn notify_to_io_error(err: notify::Error) -> io::Error {
+  match err.kind {
+    notify::ErrorKind::Io(err) => err,
+    _ => io::Error::other(err),
   }
+}
 
-  // Level `Local`
-  if exec_type == Binary {
-    if let Some(dir) = dirs::home_dir() {
-      add!(Local, dir.join(&hidden_file_name));
-      add!(Local, dir.join(&hidden_relative_file));
-    }
-    if let Some(dir) = dirs::config_local_dir() {
-      add!(Local, dir.join(&relative_file));
+/// Returns the files in `dir` whose extension equals that of a file name from `file_names`, sorted by name.
+fn list_dir_files(dir: &Path, file_names: &[FileNames]) -> io::Result<Vec<PathBuf>> {
+  let extensions: Vec<_> =
+    file_names.iter().map(|n| Path::new(&n.bare_file_name).extension().map(OsStr::to_owned)).collect();
+  let mut ret = Vec::new();
+  for entry in fs::read_dir(dir)? {
+    let path = entry?.path();
+    let is_match = extensions.iter().any(|ext| ext.is_none() || path.extension() == ext.as_deref());
+    if is_match && path.is_file() {
+      ret.push(path);
     }
   }
+  ret.sort();
+  Ok(ret)
+}
 
-  // Level `User`
-  if exec_type == Binary && let Some(dir) = dirs::config_dir() {
-    add!(User, dir.join(&relative_file));
-  }
+/// Checks if `file_name` matches `glob`. See [`find_config_glob`] for the syntax supported.
+fn matches_glob(glob: &str, file_name: &str) -> bool {
+  let glob: Vec<char> = glob.chars().collect();
+  let file_name: Vec<char> = file_name.chars().collect();
+  let (mut i, mut j) = (0, 0);
+  // If a mismatch occurs after a `*`, we backtrack and let that `*` match one more character. This holds the
+  // position after the `*` and the position in `file_name` to retry from
+  let mut star = None;
 
-  // Level `System`
-  if exec_type == Binary && let Some(dir) = crate::env::system_config_dir() {
-    add!(System, dir.join(&file_name));
-    add!(System, dir.join(&relative_file));
+  while j < file_name.len() {
+    match glob.get(i) {
+      Some('*') => {
+        i += 1;
+        star = Some((i, j));
+      }
+      Some(&c) if c == '?' || c == file_name[j] => {
+        i += 1;
+        j += 1;
+      }
+      _ => match star {
+        Some((star_i, star_j)) => {
+          i = star_i;
+          j = star_j + 1;
+          star = Some((star_i, j));
+        }
+        None => return false,
+      },
+    }
   }
+  glob[i..].iter().all(|&c| c == '*')
+}
 
-  // Level `Executable`
-  if exec_type == Binary {
-    add!(Executable, crate::env::inv_dir().join(&file_name));
+/// Merges `val` into `base`, see [`merge_toml`].
+#[cfg(feature = "toml")]
+fn merge_toml_value(base: &mut toml::Value, val: toml::Value) {
+  match (base, val) {
+    (toml::Value::Table(base), toml::Value::Table(val)) => {
+      for (key, val) in val {
+        match base.get_mut(&key) {
+          Some(base_val) => merge_toml_value(base_val, val),
+          None => {
+            base.insert(key, val);
+          }
+        }
+      }
+    }
+    (base, val) => *base = val,
   }
+}
 
-  // Collect existing files
-
-  // No canonical duplicates, only existing files
-  let mut files = Uvec::with_key(&|val: &(ConfigLevel, PathBuf)| dunce::canonicalize(&val.1).ok());
-  files.extend(file_paths);
-  if files.is_empty() {
-    Err(FindError::FileNotFound)
-  } else {
-    Ok(files.into_iter())
-  }
+/// Returns an iterator that probes `candidates` on demand, yielding only existing files and skipping
+/// canonical duplicates.
+///
+/// If `debug_target` is a [`Some`], each candidate is output to the target as it is probed. If `on_probe` is
+/// a [`Some`], it is called for each candidate as it is probed.
+fn probe_config_files(
+  candidates: Vec<(ConfigLevel, PathBuf)>,
+  debug_target: Option<DebugTarget>,
+  on_probe: Option<OnProbe>,
+) -> impl Iterator<Item = (ConfigLevel, PathBuf)> {
+  let mut canonical_paths = HashSet::new();
+  candidates.into_iter().filter(move |(level, path)| {
+    let is_file = path.is_file();
+    if debug_target.is_some() {
+      let level_str = format!("{level:?}");
+      let bullet = if is_file { "*" } else { "" };
+      // `Iterator::next` cannot return errors, so errors writing debug output are ignored. `stdout` is
+      // locked per candidate only, as the iterator may be held across other output
+      let _ = debug!(&mut DebugSink::new(debug_target), "{level_str:<10} | {bullet:<1} {path:?}");
+    }
+    if let Some(on_probe) = on_probe {
+      on_probe(*level, path, is_file);
+    }
+    // No canonical duplicates, only existing files
+    is_file && dunce::canonicalize(path).is_ok_and(|path| canonical_paths.insert(path))
+  })
 }
 
 fn replace_in_pattern(pattern: &str, to: &str) -> Result<String, FindError> {
@@ -486,17 +1577,17 @@ fn replace_in_pattern(pattern: &str, to: &str) -> Result<String, FindError> {
 
 /// Defines a few general-purpose environment variables that may be used from within configuration files.
 /// This calls [`set_env_vars_impl`] exactly once per process.
-fn set_env_vars(stdout: &mut Option<AutoStreamStdoutLock>, exec_type: ExecType) -> io::Result<()> {
+fn set_env_vars(sink: &mut DebugSink, exec_type: ExecType) -> io::Result<()> {
   static VAL: OnceLock<io::Result<()>> = OnceLock::new();
-  match VAL.get_or_init(|| set_env_vars_impl(stdout, exec_type)) {
+  match VAL.get_or_init(|| set_env_vars_impl(sink, exec_type)) {
     Ok(()) => Ok(()),
     Err(err) => Err(io::Error::new(err.kind(), err.to_string())),
   }
 }
 
-fn set_env_vars_impl(stdout: &mut Option<AutoStreamStdoutLock>, exec_type: ExecType) -> io::Result<()> {
+fn set_env_vars_impl(sink: &mut DebugSink, exec_type: ExecType) -> io::Result<()> {
   let mut set_env_var = |name: &str, val: &OsStr| -> io::Result<()> {
-    debug!(stdout, "Setting `{name}` to {val:?}")?;
+    debug!(sink, "Setting `{name}` to {val:?}")?;
     crate::env::set(name, Some(val));
     Ok(())
   };
@@ -522,14 +1613,386 @@ fn set_env_vars_impl(stdout: &mut Option<AutoStreamStdoutLock>, exec_type: ExecT
   Ok(())
 }
 
+/// Outputs some introductory debug info and, if requested, sets env vars.
+fn start_search(
+  sink: &mut DebugSink,
+  exec_type: ExecType,
+  set_env_vars: bool,
+) -> io::Result<()> {
+  // Some introductory debug info
+
+  debug!(sink, "Checking paths for {exec_type} executable")?;
+
+  debug!(sink, "Current directory: {}", {
+    match std::env::current_dir() {
+      Ok(dir) => format!("{dir:?}"),
+      Err(_) => String::from("-"),
+    }
+  })?;
+
+  // If requested, set env vars. This is executed only once
+
+  if set_env_vars {
+    self::set_env_vars(sink, exec_type)?;
+  }
+
+  Ok(())
+}
+
 // Tests ====================================================================================================
 
 #[cfg(test)]
 mod tests {
+  use std::cell::RefCell;
+
   use super::*;
 
+  // `ConfigFinder` -----------------------------------------------------------------------------------------
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_config_finder() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let dir_path = dunce::canonicalize(dir.path())?;
+    fs::write(dir_path.join(".meadows-test.config.toml"), "")?;
+    fs::write(dir_path.join(".meadows-test.config.json"), "")?;
+
+    let finder =
+      ConfigFinder::new(ExecType::UnitTest).name("meadows-test").paths(Some(&dir_path)).set_env_vars(false);
+    assert_eq!(finder.find_one()?, (ConfigLevel::Path, dir_path.join(".meadows-test.config.toml")));
+
+    // Debug information does not change the result, whatever its target
+    for debug_target in [DebugTarget::Stdout, DebugTarget::Tracing] {
+      let (level, path) = finder.clone().debug(true).debug_target(debug_target).find_one()?;
+      assert_eq!((level, path), (ConfigLevel::Path, dir_path.join(".meadows-test.config.toml")));
+    }
+
+    let finder_multi = finder.clone().file_name_patterns(&["{}config.json", "{}config.toml"]);
+    let files: Vec<_> = finder_multi.find_all()?.into_iter().collect();
+    assert_eq!(files, vec![
+      (ConfigLevel::Path, dir_path.join(".meadows-test.config.json")),
+      (ConfigLevel::Path, dir_path.join(".meadows-test.config.toml")),
+    ]);
+
+    let result = finder.clone().file_name_pattern("{}config.yaml").find_one();
+    assert!(matches!(result, Err(FindError::FileNotFound { .. })));
+
+    // Excluded levels are not probed
+    let probed = RefCell::new(Vec::new());
+    let on_probe = |level, path: &Path, _| probed.borrow_mut().push((level, path.to_owned()));
+    let excluded_levels = [ConfigLevel::Path, ConfigLevel::Package];
+    let result = finder.clone().exclude_levels(&excluded_levels).on_probe(&on_probe).find_one();
+    assert!(matches!(result, Err(FindError::FileNotFound { probed }) if probed.is_empty()));
+    assert!(probed.into_inner().is_empty());
+
+    // The search stops at the first existing file
+    let probed = RefCell::new(Vec::new());
+    let on_probe = |level, path: &Path, is_file| probed.borrow_mut().push((level, path.to_owned(), is_file));
+    finder.clone().file_name_patterns(&["{}config.yaml", "{}config.toml"]).on_probe(&on_probe).find_one()?;
+    assert_eq!(probed.into_inner(), vec![
+      (ConfigLevel::Path, dir_path.join(".meadows-test.config.yaml"), false),
+      (ConfigLevel::Path, dir_path.join(".meadows-test.config.toml"), true),
+    ]);
+
+    // Level `Instance` is searched for examples, but not for tests
+    let instance_dir = std::env::current_dir()?;
+    let instance_path = instance_dir.join(".meadows-test.config.yaml");
+    for (exec_type, is_searched) in [(ExecType::Example, true), (ExecType::UnitTest, false)] {
+      let probed = RefCell::new(Vec::new());
+      let on_probe = |level, path: &Path, _| probed.borrow_mut().push((level, path.to_owned()));
+      let _ = finder
+        .clone()
+        .exec_type(exec_type)
+        .file_name_pattern("{}config.yaml")
+        .exclude_levels(&[ConfigLevel::Path])
+        .on_probe(&on_probe)
+        .find_one();
+      assert_eq!(probed.into_inner().contains(&(ConfigLevel::Instance, instance_path.clone())), is_searched);
+    }
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_config_finder_expand_dirs() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let dir_path = dunce::canonicalize(dir.path())?;
+    let conf_d = dir_path.join("conf.d");
+    let other_dir = dir_path.join("other");
+    fs::create_dir_all(conf_d.join("sub.toml"))?;
+    fs::create_dir(&other_dir)?;
+    for file_name in ["20-local.toml", "10-base.toml", ".meadows-test.config.toml", "notes.txt"] {
+      fs::write(conf_d.join(file_name), "")?;
+    }
+    fs::write(other_dir.join(".meadows-test.config.toml"), "")?;
+
+    let paths = std::env::join_paths([&conf_d, &other_dir])?;
+    let finder = ConfigFinder::new(ExecType::UnitTest)
+      .name("meadows-test")
+      .paths(Some(&paths))
+      .exclude_levels(&[ConfigLevel::Package])
+      .set_env_vars(false);
+    let files: Vec<_> = finder.clone().find_all()?.into_iter().map(|(_, path)| path).collect();
+    let expected = [conf_d.join(".meadows-test.config.toml"), other_dir.join(".meadows-test.config.toml")];
+    assert_eq!(files, expected);
+
+    // Files of a directory follow its single-file paths, without duplicates
+    let files: Vec<_> = finder.expand_dirs(true).find_all()?.into_iter().map(|(_, path)| path).collect();
+    assert_eq!(files, [
+      conf_d.join(".meadows-test.config.toml"),
+      conf_d.join("10-base.toml"),
+      conf_d.join("20-local.toml"),
+      other_dir.join(".meadows-test.config.toml"),
+    ]);
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_config_finder_find_glob() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let dir_path = dunce::canonicalize(dir.path())?;
+    let config_dir = dir_path.join(".meadows-test");
+    fs::create_dir(&config_dir)?;
+    fs::write(config_dir.join("a.plugin.toml"), "")?;
+    // A directory that cannot be listed is skipped
+    let other_dir = dir_path.join("other");
+    fs::create_dir(&other_dir)?;
+    fs::write(other_dir.join(".meadows-test"), "")?;
+    let paths = std::env::join_paths([other_dir.clone(), dir_path.clone()])?;
+
+    let probed = RefCell::new(Vec::new());
+    let on_probe = |_: ConfigLevel, path: &Path, is_listed: bool| {
+      probed.borrow_mut().push((path.to_owned(), is_listed));
+    };
+    let finder = ConfigFinder::new(ExecType::UnitTest)
+      .name("meadows-test")
+      .on_probe(&on_probe)
+      .paths(Some(&paths))
+      .set_env_vars(false);
+
+    let files = finder.find_glob("*.plugin.toml")?;
+    assert_eq!(files, [(ConfigLevel::Path, config_dir.join("a.plugin.toml"))]);
+    assert_eq!(*probed.borrow(), [(other_dir.join(".meadows-test"), false), (config_dir, true)]);
+
+    let result = finder.exclude_levels(&[ConfigLevel::Path]).find_glob("*.plugin.toml");
+    assert!(matches!(result, Err(FindError::FileNotFound { probed }) if probed.is_empty()));
+
+    Ok(())
+  }
+
   // Functions ----------------------------------------------------------------------------------------------
 
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_config_dir_for() -> io::Result<()> {
+    use ConfigLevel::*;
+
+    let name = OsStr::new("meadows-test");
+
+    assert_eq!(config_dir_for(Path, ExecType::Binary, name), None);
+    assert_eq!(
+      config_dir_for(Instance, ExecType::Example, name),
+      Some(std::env::current_dir()?.join(".meadows-test"))
+    );
+    assert_eq!(config_dir_for(Instance, ExecType::UnitTest, name), None);
+
+    // `cargo test` sets `CARGO_MANIFEST_DIR`
+    let manifest_dir = PathBuf::from(crate::env::get("CARGO_MANIFEST_DIR").unwrap());
+    assert_eq!(config_dir_for(Package, ExecType::Binary, name), Some(manifest_dir.join("src")));
+    assert_eq!(config_dir_for(Package, ExecType::IntegTest, name), Some(manifest_dir.join("tests")));
+
+    assert_eq!(config_dir_for(User, ExecType::Binary, name), dirs::config_dir().map(|dir| dir.join(name)));
+    assert_eq!(config_dir_for(Executable, ExecType::Binary, name).as_ref(), Some(crate::env::inv_dir()));
+    for level in [Local, User, System, Executable] {
+      assert_eq!(config_dir_for(level, ExecType::UnitTest, name), None);
+    }
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_find_config_file_not_found() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let dir_path = dunce::canonicalize(dir.path())?;
+
+    let result = find_config_file(
+      ExecType::UnitTest,
+      "{}config.toml",
+      false,
+      OsStr::new("meadows-test"),
+      Some(&dir_path),
+      false,
+    );
+    let Err(FindError::FileNotFound { probed }) = result else {
+      panic!("Expected `FindError::FileNotFound`, got {result:?}");
+    };
+    // Paths at level `Path` come first, possibly followed by paths at level `Package`
+    assert_eq!(probed[..2], [
+      dir_path.join(".meadows-test.config.toml"),
+      dir_path.join(".meadows-test").join("config.toml"),
+    ]);
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_find_config_glob() -> anyhow::Result<()> {
+    let name = OsStr::new("meadows-test");
+    let dir = tempfile::tempdir()?;
+    let dir_path = dunce::canonicalize(dir.path())?;
+    let config_dir = dir_path.join(".meadows-test");
+    fs::create_dir(&config_dir)?;
+    for file in ["c.plugin.toml", "a.plugin.toml", "b.plugin.toml", "config.toml"] {
+      fs::write(config_dir.join(file), "")?;
+    }
+
+    let files = find_config_glob(ExecType::UnitTest, "*.plugin.toml", false, name, Some(&dir_path), false)?;
+    assert_eq!(files, vec![
+      (ConfigLevel::Path, config_dir.join("a.plugin.toml")),
+      (ConfigLevel::Path, config_dir.join("b.plugin.toml")),
+      (ConfigLevel::Path, config_dir.join("c.plugin.toml")),
+    ]);
+
+    let result = find_config_glob(ExecType::UnitTest, "*.json", false, name, Some(&dir_path), false);
+    assert!(matches!(result, Err(FindError::FileNotFound { probed }) if probed == vec![config_dir]));
+
+    let result = find_config_glob(ExecType::UnitTest, "{}*.toml", false, name, Some(&dir_path), false);
+    assert!(matches!(result, Err(FindError::InvalidFileNamePattern(_))));
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_find_config_files_lazy() -> anyhow::Result<()> {
+    let name = OsStr::new("meadows-test");
+    let dir = tempfile::tempdir()?;
+    let dir_path = dunce::canonicalize(dir.path())?;
+    fs::create_dir(dir_path.join(".meadows-test"))?;
+    fs::write(dir_path.join(".meadows-test.config.toml"), "")?;
+    // The same directory twice yields canonical duplicates
+    let paths = std::env::join_paths([dir_path.clone(), dir_path.join(".")])?;
+
+    let mut files =
+      find_config_files_lazy(ExecType::UnitTest, "{}config.toml", false, name, Some(&paths), false)?;
+    assert_eq!(files.next(), Some((ConfigLevel::Path, dir_path.join(".meadows-test.config.toml"))));
+
+    // Files are probed on demand, so a file created now is still found
+    fs::write(dir_path.join(".meadows-test").join("config.toml"), "")?;
+    assert_eq!(files.next(), Some((ConfigLevel::Path, dir_path.join(".meadows-test").join("config.toml"))));
+    assert_eq!(files.next(), None);
+
+    // Paths are reported to `on_probe` as they are probed
+    let probed = RefCell::new(Vec::new());
+    let on_probe = |_, path: &Path, _| probed.borrow_mut().push(path.to_owned());
+    let finder = ConfigFinder::new(ExecType::UnitTest)
+      .file_name_pattern("{}config.toml")
+      .name(name)
+      .on_probe(&on_probe)
+      .paths(Some(&paths))
+      .set_env_vars(false);
+    let mut files = finder.find_lazy()?;
+    assert_eq!(files.next(), Some((ConfigLevel::Path, dir_path.join(".meadows-test.config.toml"))));
+    assert_eq!(*probed.borrow(), [dir_path.join(".meadows-test.config.toml")]);
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_find_config_files_multi() -> anyhow::Result<()> {
+    let name = OsStr::new("meadows-test");
+    let dir = tempfile::tempdir()?;
+    let dir_path = dunce::canonicalize(dir.path())?;
+    fs::create_dir(dir_path.join(".meadows-test"))?;
+    for file in [".meadows-test.config.json", ".meadows-test.config.toml", ".meadows-test/config.yaml"] {
+      fs::write(dir_path.join(file), "")?;
+    }
+
+    let files: Vec<_> = find_config_files_multi(
+      ExecType::UnitTest,
+      &["{}config.toml", "{}config.yaml", "{}config.json"],
+      false,
+      name,
+      Some(&dir_path),
+      false,
+    )?
+    .into_iter()
+    .collect();
+    assert_eq!(files, vec![
+      (ConfigLevel::Path, dir_path.join(".meadows-test.config.toml")),
+      (ConfigLevel::Path, dir_path.join(".meadows-test.config.json")),
+      (ConfigLevel::Path, dir_path.join(".meadows-test/config.yaml")),
+    ]);
+
+    let result = find_config_files_multi(ExecType::UnitTest, &[], false, name, Some(&dir_path), false);
+    assert!(matches!(result, Err(FindError::InvalidFileNamePattern(_))));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_expand_env_vars() -> Result<(), FindError> {
+    let _guard = crate::env::set_scoped("MEADOWS_TEST_EXPAND", Some("val"));
+
+    assert_eq!(expand_env_vars(PathBuf::from("/a/b"))?, PathBuf::from("/a/b"));
+    assert_eq!(expand_env_vars(PathBuf::from("${MEADOWS_TEST_EXPAND}/cfg"))?, PathBuf::from("val/cfg"));
+    assert_eq!(expand_env_vars(PathBuf::from("$MEADOWS_TEST_EXPAND/cfg"))?, PathBuf::from("val/cfg"));
+    assert_eq!(
+      expand_env_vars(PathBuf::from("/${MEADOWS_TEST_EXPAND}/${MEADOWS_TEST_EXPAND}"))?,
+      PathBuf::from("/val/val")
+    );
+    assert_eq!(expand_env_vars(PathBuf::from("/a/${unterminated"))?, PathBuf::from("/a/${unterminated"));
+
+    let result = expand_env_vars(PathBuf::from("/${MEADOWS_TEST_UNDEFINED}"));
+    assert!(matches!(result, Err(FindError::UndefinedEnvVar(name)) if name == "MEADOWS_TEST_UNDEFINED"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_matches_glob() {
+    assert!(matches_glob("*.plugin.toml", "a.plugin.toml"));
+    assert!(matches_glob("*.plugin.toml", ".plugin.toml"));
+    assert!(matches_glob("?.toml", "ä.toml"));
+    assert!(matches_glob("*a*b", "xaybab"));
+    assert!(matches_glob("**", ""));
+    assert!(matches_glob("config.toml", "config.toml"));
+
+    assert!(!matches_glob("*.plugin.toml", "a.plugin.json"));
+    assert!(!matches_glob("?.toml", "ab.toml"));
+    assert!(!matches_glob("*a*b", "xaybaba"));
+    assert!(!matches_glob("config.toml", "Config.toml"));
+  }
+
+  #[cfg(feature = "toml")]
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_merge_toml() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let high_path = dir.path().join("high.toml");
+    let low_path = dir.path().join("low.toml");
+    fs::write(&high_path, "name = \"high\"\nlist = [3]\n[table]\nb = 20\nc = 30\n")?;
+    fs::write(&low_path, "name = \"low\"\nlist = [1, 2]\nonly_low = true\n[table]\na = 1\nb = 2\n")?;
+
+    let config = merge_toml([(ConfigLevel::Path, high_path), (ConfigLevel::User, low_path.clone())])?;
+    let expected: toml::Table = toml::from_str(
+      "name = \"high\"\nlist = [3]\nonly_low = true\n[table]\na = 1\nb = 20\nc = 30\n",
+    )?;
+    assert_eq!(config, toml::Value::Table(expected));
+
+    fs::write(&low_path, "invalid =")?;
+    let result = merge_toml([(ConfigLevel::User, low_path)]);
+    assert!(matches!(result, Err(FindError::Toml(..))));
+
+    Ok(())
+  }
+
   #[test]
   fn test_replace_in_pattern() -> Result<(), FindError> {
     assert!(matches!(replace_in_pattern("", "name"), Err(FindError::InvalidFileNamePattern(_))));
@@ -549,6 +2012,50 @@ mod tests {
 
     Ok(())
   }
+
+  #[cfg(feature = "notify")]
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_watch() -> anyhow::Result<()> {
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::Duration;
+
+    let dir = tempfile::tempdir()?;
+    let path = dunce::canonicalize(dir.path())?.join("config.toml");
+    fs::write(&path, "a = 1")?;
+
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let handle = {
+      let changes = Arc::clone(&changes);
+      watch(&path, move |path| changes.lock().unwrap().push(path.to_owned()))?
+    };
+    let wait = || thread::sleep(WATCH_DEBOUNCE * 5);
+
+    // Several quick writes are reported once
+    for i in 0..3 {
+      fs::write(&path, format!("a = {i}"))?;
+    }
+    fs::write(dir.path().join("other.toml"), "")?;
+    wait();
+    assert_eq!(*changes.lock().unwrap(), vec![path.clone()]);
+
+    // An atomic replace is detected
+    let tmp_path = dir.path().join("config.toml.tmp");
+    fs::write(&tmp_path, "a = 4")?;
+    fs::rename(&tmp_path, &path)?;
+    wait();
+    assert_eq!(changes.lock().unwrap().len(), 2);
+
+    // No changes are reported after the handle has been dropped
+    drop(handle);
+    fs::write(&path, "a = 5")?;
+    thread::sleep(Duration::from_millis(500));
+    assert_eq!(changes.lock().unwrap().len(), 2);
+
+    Ok(())
+  }
 }
 
 // EOF
@@ -2,23 +2,246 @@
 
 //! Environment-related utilities.
 
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
 use std::env;
 use std::ffi::OsStr;
 use std::ffi::OsString;
+use std::fs;
 use std::io;
 use std::io::prelude::*;
+use std::path::Path;
 use std::path::PathBuf;
-use std::sync::{Mutex, OnceLock};
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock, PoisonError};
 
 use regex::Regex;
+use thiserror::Error as ThisError;
 
 // Variables ------------------------------------------------------------------------------------------------
 
 /// Thread-safe mutex for synchronizing environment-variable operations.
 static ENV_MUTEX: OnceLock<Mutex<()>> = OnceLock::new();
 
+// `EnvSnapshot` --------------------------------------------------------------------------------------------
+
+/// A snapshot of the whole environment, as returned by [`snapshot`], that restores the environment when
+/// dropped.
+///
+/// When the snapshot is dropped or [`restore`](EnvSnapshot::restore) is called, variables added since the
+/// snapshot was taken are removed, and variables changed or removed since are reset to their recorded
+/// values. This is the heavy-hammer alternative to a [`ScopedVar`] for each variable, e.g. for integration
+/// tests that change many variables.
+///
+/// Taking a snapshot clones all variables, and restoring it compares all variables, so both operations take
+/// time linear in the size of the environment.
+///
+/// # Safety
+///
+/// All environment-variable operations from this module are thread-safe as long as they are used
+/// exclusively. Note that a snapshot covers the whole process: Restoring it also reverts changes made by
+/// other threads in the meantime, e.g. by tests running in parallel.
+#[derive(Debug)]
+#[must_use = "the environment is restored immediately if the snapshot is not used"]
+pub struct EnvSnapshot {
+  vars: BTreeMap<OsString, OsString>,
+}
+
+impl EnvSnapshot {
+  /// Returns the changes needed to turn the `current` variables into the recorded ones, in the order of the
+  /// keys. A [`None`] value means that the variable is to be removed.
+  fn changes<I>(&self, current: I) -> Vec<(OsString, Option<OsString>)>
+  where
+    I: IntoIterator<Item = (OsString, OsString)>, {
+    let current: BTreeMap<_, _> = current.into_iter().collect();
+    let mut ret: Vec<_> =
+      current.keys().filter(|key| !self.vars.contains_key(*key)).map(|key| (key.clone(), None)).collect();
+    for (key, val) in &self.vars {
+      if current.get(key) != Some(val) {
+        ret.push((key.clone(), Some(val.clone())));
+      }
+    }
+    ret.sort();
+    ret
+  }
+
+  /// Restores the environment to the state recorded by the snapshot.
+  ///
+  /// This is the same as dropping the snapshot, but makes the intent explicit.
+  pub fn restore(self) { drop(self); }
+}
+
+impl Drop for EnvSnapshot {
+  fn drop(&mut self) {
+    let _guard = env_mutex().lock().unwrap_or_else(PoisonError::into_inner);
+    for (key, val) in self.changes(env::vars_os()) {
+      set_impl(key, val);
+    }
+  }
+}
+
+// `ExpandError` --------------------------------------------------------------------------------------------
+
+/// An error returned by [`try_expand`].
+#[derive(Debug, ThisError)]
+pub enum ExpandError {
+  /// An environment variable referenced is not defined.
+  #[error("Environment variable `{0}` is not defined")]
+  UndefinedVar(String),
+}
+
+// `ScopedVar` ----------------------------------------------------------------------------------------------
+
+/// A guard that restores an environment variable when dropped, as returned by [`set_scoped`].
+///
+/// When the guard is dropped, the variable is set to the value it had before [`set_scoped`] was called, or
+/// removed if it was not set.
+///
+/// Guards for the same variable may overlap. Since each guard restores the value it has recorded, the
+/// original value is only restored reliably if the guards are dropped in reverse order of creation (LIFO),
+/// which is the case for guards bound to local variables in nested scopes.
+///
+/// # Safety
+///
+/// All environment-variable operations from this module are thread-safe as long as they are used
+/// exclusively.
+#[derive(Debug)]
+#[must_use = "the variable is restored immediately if the guard is not used"]
+pub struct ScopedVar {
+  key: OsString,
+  old_value: Option<OsString>,
+}
+
+impl Drop for ScopedVar {
+  fn drop(&mut self) { set(&self.key, self.old_value.take()); }
+}
+
+// `XdgDirs` ------------------------------------------------------------------------------------------------
+
+/// The XDG base directories, as returned by [`xdg_dirs`].
+///
+/// On Unix, each directory is taken from an environment variable as defined by the XDG Base Directory
+/// Specification. As required by the specification, a value that is not an absolute path is ignored, and
+/// the directory falls back to a path relative to the user's home directory, as returned by
+/// [`dirs::home_dir`]. On Windows, each directory maps to the closest equivalent as returned by the `dirs`
+/// crate:
+///
+/// | Field         | Unix Variable     | Unix Fallback        | Windows
+/// | :------------ | :---------------- | :------------------- | :------
+/// | `cache_home`  | `XDG_CACHE_HOME`  | `$HOME/.cache`       | [`dirs::cache_dir`], e.g. `C:\Users\Alice\AppData\Local`
+/// | `config_home` | `XDG_CONFIG_HOME` | `$HOME/.config`      | [`dirs::config_dir`], e.g. `C:\Users\Alice\AppData\Roaming`
+/// | `data_home`   | `XDG_DATA_HOME`   | `$HOME/.local/share` | [`dirs::data_dir`], e.g. `C:\Users\Alice\AppData\Roaming`
+/// | `runtime_dir` | `XDG_RUNTIME_DIR` | None                 | None
+/// | `state_home`  | `XDG_STATE_HOME`  | `$HOME/.local/state` | [`dirs::data_local_dir`], e.g. `C:\Users\Alice\AppData\Local`
+///
+/// A field is [`None`] if neither the variable nor the fallback is available, e.g. if the user's home
+/// directory cannot be determined.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct XdgDirs {
+  /// The base directory for user-specific non-essential (cached) data.
+  pub cache_home: Option<PathBuf>,
+  /// The base directory for user-specific configuration files.
+  pub config_home: Option<PathBuf>,
+  /// The base directory for user-specific data files.
+  pub data_home: Option<PathBuf>,
+  /// The base directory for user-specific runtime files, such as sockets.
+  pub runtime_dir: Option<PathBuf>,
+  /// The base directory for user-specific state files, such as logs and history.
+  pub state_home: Option<PathBuf>,
+}
+
 // Functions ------------------------------------------------------------------------------------------------
 
+/// Builds an environment for a child process, based on the current environment.
+///
+/// The function takes a snapshot of the current environment and applies the `overrides` in the given order.
+/// For each pair of a key and a value, the variable is set to the value if it is a [`Some`], or removed if
+/// it is [`None`]. The current environment is not modified.
+///
+/// The result is sorted by key, so it is reproducible. It is meant to be passed to
+/// [`Command::envs`](std::process::Command::envs) after calling
+/// [`Command::env_clear`](std::process::Command::env_clear).
+///
+/// # Safety
+///
+/// All environment-variable operations from this module are thread-safe as long as they are used
+/// exclusively.
+///
+/// # Examples
+///
+/// ```
+/// use std::process::Command;
+///
+/// use meadows::env;
+///
+/// let mut command = Command::new("meadows-env");
+/// command.env_clear().envs(env::child_env(&[("MY_VAR", Some("my_value")), ("NO_COLOR", None)]));
+/// ```
+#[must_use]
+pub fn child_env(overrides: &[(&str, Option<&str>)]) -> Vec<(OsString, OsString)> {
+  let mut ret: BTreeMap<OsString, OsString> = vars().collect();
+  for &(key, val) in overrides {
+    match val {
+      Some(val) => ret.insert(key.into(), val.into()),
+      None => ret.remove(OsStr::new(key)),
+    };
+  }
+  ret.into_iter().collect()
+}
+
+/// Checks if colored output should be enabled.
+///
+/// This follows the same rules that [`anstream`] applies to configure [`crate::io::stdout`] and
+/// [`crate::io::stderr`], so output that is colored manually matches the crate's streams. `is_tty` tells
+/// whether the output is written to a terminal.
+///
+/// The following rules are checked in order, the first matching rule wins:
+///
+/// | Environment Variable | Value              | Result
+/// | :------------------- | :----------------- | :-----
+/// | `NO_COLOR`           | Set, but not empty | `false`
+/// | `CLICOLOR_FORCE`     | Set, but not `0`   | `true`
+/// | `CLICOLOR`           | `0`                | `false`
+/// | -                    | -                  | `is_tty`
+///
+/// # Safety
+///
+/// All environment-variable operations from this module are thread-safe as long as they are used
+/// exclusively.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::IsTerminal;
+///
+/// use meadows::env;
+///
+/// let is_colored = env::color_enabled(std::io::stdout().is_terminal());
+/// ```
+#[must_use]
+pub fn color_enabled(is_tty: bool) -> bool {
+  color_enabled_impl(get("NO_COLOR"), get("CLICOLOR_FORCE"), get("CLICOLOR"), is_tty)
+}
+
+fn color_enabled_impl(
+  no_color: Option<OsString>,
+  clicolor_force: Option<OsString>,
+  clicolor: Option<OsString>,
+  is_tty: bool,
+) -> bool {
+  if no_color.is_some_and(|val| !val.is_empty()) {
+    return false;
+  }
+  if clicolor_force.is_some_and(|val| val != "0") {
+    return true;
+  }
+  if clicolor.is_some_and(|val| val == "0") {
+    return false;
+  }
+  is_tty
+}
+
 /// Returns the canonical directory of the executable.
 ///
 /// # Panics
@@ -53,6 +276,108 @@ pub fn dump() -> io::Result<()> {
 
 fn env_mutex() -> &'static Mutex<()> { ENV_MUTEX.get_or_init(|| Mutex::new(())) }
 
+/// Expands references to environment variables in a string.
+///
+/// The following references are replaced by the value of the environment variable `NAME`:
+///
+/// | Reference | Remarks
+/// | :-------- | :------
+/// | `${NAME}` | `NAME` is any non-empty string not containing `}`
+/// | `$NAME`   | `NAME` is the longest sequence of ASCII letters, digits, and `_`, not starting with a digit
+/// | `%NAME%`  | Windows only. `NAME` is any non-empty string not containing `%`
+///
+/// `$$` is replaced by a literal `$`. A `$` or `%` that does not start a reference is kept as is. A
+/// reference to an undefined variable is kept as is as well. To treat it as an error instead, use
+/// [`try_expand`]. Values that are not valid Unicode are converted lossily.
+///
+/// If `input` contains no references, it is returned as a [`Cow::Borrowed`].
+///
+/// # Safety
+///
+/// All environment-variable operations from this module are thread-safe as long as they are used
+/// exclusively.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::env;
+///
+/// env::set("MY_VAR", Some("my_value"));
+/// assert_eq!(env::expand("${MY_VAR}/$MY_VAR/$$MY_VAR"), "my_value/my_value/$MY_VAR");
+/// assert_eq!(env::expand("${MY_UNDEFINED_VAR}"), "${MY_UNDEFINED_VAR}");
+/// ```
+#[allow(clippy::missing_panics_doc)]
+#[must_use]
+pub fn expand(input: &str) -> Cow<'_, str> {
+  let _guard = env_mutex().lock().unwrap();
+  let Ok(ret) = expand_impl(input, |name| Ok::<_, Infallible>(env::var_os(name)), cfg!(windows));
+  ret
+}
+
+/// Expands the variable references in `input`, looking up each variable with `lookup`. A reference is kept
+/// if `lookup` returns `Ok(None)`, and expanding fails as soon as `lookup` returns an [`Err`].
+fn expand_impl<F, E>(input: &str, lookup: F, is_windows: bool) -> Result<Cow<'_, str>, E>
+where
+  F: Fn(&str) -> Result<Option<OsString>, E>, {
+  let is_start = |c: char| c == '$' || (is_windows && c == '%');
+  if !input.contains(is_start) {
+    return Ok(Cow::Borrowed(input));
+  }
+
+  let mut ret = String::with_capacity(input.len());
+  let mut remaining = input;
+  while let Some(start) = remaining.find(is_start) {
+    ret.push_str(&remaining[..start]);
+    remaining = &remaining[start..];
+
+    if remaining.starts_with("$$") {
+      ret.push('$');
+      remaining = &remaining[2..];
+      continue;
+    }
+    let Some((name, len)) = var_ref(remaining) else {
+      ret.push_str(&remaining[..1]);
+      remaining = &remaining[1..];
+      continue;
+    };
+    match lookup(name)? {
+      Some(val) => ret.push_str(&val.to_string_lossy()),
+      None => ret.push_str(&remaining[..len]),
+    }
+    remaining = &remaining[len..];
+  }
+  ret.push_str(remaining);
+  Ok(Cow::Owned(ret))
+}
+
+/// Replaces a leading `~` in a path with the user's home directory.
+///
+/// Only a `~` that makes up the first component of the path is replaced, i.e. `~` and `~/dir`. A `~` in any
+/// other place is kept, as is the `~user` syntax, which is not supported. If the home directory cannot be
+/// determined via [`dirs::home_dir`], the path is returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use meadows::env;
+///
+/// let path = env::expand_tilde(Path::new("~/.config"));
+/// assert_eq!(path, dirs::home_dir().unwrap().join(".config"));
+/// assert_eq!(env::expand_tilde(Path::new("a/~/b")), Path::new("a/~/b"));
+/// ```
+#[must_use]
+pub fn expand_tilde(path: &Path) -> PathBuf { expand_tilde_impl(path, dirs::home_dir()) }
+
+fn expand_tilde_impl(path: &Path, home_dir: Option<PathBuf>) -> PathBuf {
+  match (path.strip_prefix("~"), home_dir) {
+    (Ok(remaining), Some(home_dir)) if remaining.as_os_str().is_empty() => home_dir,
+    (Ok(remaining), Some(home_dir)) => home_dir.join(remaining),
+    _ => path.to_owned(),
+  }
+}
+
 /// A thread-safe replacement for [`env::var_os`].
 ///
 /// # Safety
@@ -66,6 +391,69 @@ pub fn get<K: AsRef<OsStr>>(key: K) -> Option<OsString> {
   env::var_os(key)
 }
 
+/// Returns the value of an environment variable as a [`bool`].
+///
+/// The following values are accepted, ignoring ASCII case. Leading and trailing whitespace is ignored as
+/// well:
+///
+/// | Value                     | Result
+/// | :------------------------ | :-----
+/// | `1`, `true`, `yes`, `on`  | `Some(true)`
+/// | `0`, `false`, `no`, `off` | `Some(false)`
+///
+/// If the variable is not set or has any other value, [`None`] is returned.
+///
+/// # Safety
+///
+/// All environment-variable operations from this module are thread-safe as long as they are used
+/// exclusively.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::env;
+///
+/// env::set("MY_VAR", Some("Yes"));
+/// assert_eq!(env::get_bool("MY_VAR"), Some(true));
+/// ```
+#[must_use]
+pub fn get_bool<K: AsRef<OsStr>>(key: K) -> Option<bool> { parse_bool(&get(key)?) }
+
+fn parse_bool(val: &OsStr) -> Option<bool> {
+  let val = val.to_str()?.trim();
+  if ["1", "true", "yes", "on"].iter().any(|s| val.eq_ignore_ascii_case(s)) {
+    Some(true)
+  } else if ["0", "false", "no", "off"].iter().any(|s| val.eq_ignore_ascii_case(s)) {
+    Some(false)
+  } else {
+    None
+  }
+}
+
+/// Returns the value of an environment variable, parsed via [`str::parse`].
+///
+/// Returns [`None`] if the variable is not set. Otherwise, the result of parsing the value is returned. A
+/// value that is not valid Unicode is converted lossily before being parsed. No whitespace is trimmed.
+///
+/// # Safety
+///
+/// All environment-variable operations from this module are thread-safe as long as they are used
+/// exclusively.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::env;
+///
+/// env::set("MY_VAR", Some("42"));
+/// assert_eq!(env::get_parsed::<_, u32>("MY_VAR"), Some(Ok(42)));
+/// assert!(env::get_parsed::<_, u32>("MY_UNDEFINED_VAR").is_none());
+/// ```
+#[must_use]
+pub fn get_parsed<K: AsRef<OsStr>, T: FromStr>(key: K) -> Option<Result<T, T::Err>> {
+  Some(get(key)?.to_string_lossy().parse())
+}
+
 /// Returns the invocation directory of the executable.
 ///
 /// # Panics
@@ -117,6 +505,108 @@ pub fn inv_path() -> &'static PathBuf {
   VAL.get_or_init(|| PathBuf::from(env::args_os().next().unwrap()))
 }
 
+/// Loads environment variables from a dotenv-style file, e.g. `.env`.
+///
+/// Each line of the file is parsed as follows:
+///
+/// - Empty lines and lines starting with `#` are skipped. Leading and trailing whitespace is ignored.
+/// - Other lines must have the form `KEY=VALUE`, optionally preceded by `export `. Whitespace around `KEY`
+///   and `VALUE` is ignored. `KEY` must not be empty or contain whitespace.
+/// - If `VALUE` is enclosed in single quotes, its content is taken literally.
+/// - If `VALUE` is enclosed in double quotes, the escape sequences `\n`, `\r`, `\t`, `\"`, and `\\` are
+///   replaced within its content. Other backslashes are kept.
+/// - Otherwise, `VALUE` is taken as is, up to a `#` that is preceded by whitespace, which starts a comment.
+///
+/// Values are not expanded. To expand references to other variables, use [`expand`] after loading.
+///
+/// The variables are set in file order via [`set`]. If `override_existing` is `false`, variables that are
+/// already set, including ones set by a previous line of the same file, are left unchanged. Otherwise, the
+/// file takes precedence, and a later line overrides an earlier one.
+///
+/// Returns the number of variables set.
+///
+/// # Safety
+///
+/// All environment-variable operations from this module are thread-safe as long as they are used
+/// exclusively.
+///
+/// # Errors
+///
+/// Returns [`Err`] if the file cannot be read, or with [`io::ErrorKind::InvalidData`] if a line cannot be
+/// parsed. In the latter case, no variable is set.
+///
+/// # Examples
+///
+/// ```no_run
+/// use meadows::env;
+///
+/// let count = env::load_dotenv(".env", false)?;
+/// println!("{count} variables loaded");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn load_dotenv<P: AsRef<Path>>(path: P, override_existing: bool) -> io::Result<usize> {
+  let text = fs::read_to_string(path)?;
+  let mut ret = 0;
+  for (key, val) in parse_dotenv(&text)? {
+    if override_existing || get(&key).is_none() {
+      set(&key, Some(&val));
+      ret += 1;
+    }
+  }
+  Ok(ret)
+}
+
+/// Parses the text of a dotenv-style file into key-value pairs. See [`load_dotenv`].
+fn parse_dotenv(text: &str) -> io::Result<Vec<(String, String)>> {
+  let mut ret = Vec::new();
+  for (i, line) in text.lines().enumerate() {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+    let error = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, format!("Line {}: {msg}", i + 1));
+
+    let line = line.strip_prefix("export ").map_or(line, str::trim_start);
+    let (key, val) = line.split_once('=').ok_or_else(|| error("Missing `=`"))?;
+    let key = key.trim_end();
+    if key.is_empty() || key.contains(char::is_whitespace) {
+      return Err(error("Invalid key"));
+    }
+
+    let val = val.trim_start();
+    let val = if let Some(quoted) = val.strip_prefix('\'') {
+      let end = quoted.find('\'').ok_or_else(|| error("Missing closing `'`"))?;
+      quoted[..end].to_owned()
+    } else if let Some(quoted) = val.strip_prefix('"') {
+      let mut unquoted = String::new();
+      let mut chars = quoted.chars();
+      loop {
+        match chars.next().ok_or_else(|| error("Missing closing `\"`"))? {
+          '"' => break,
+          '\\' => match chars.next() {
+            Some('n') => unquoted.push('\n'),
+            Some('r') => unquoted.push('\r'),
+            Some('t') => unquoted.push('\t'),
+            Some(c @ ('"' | '\\')) => unquoted.push(c),
+            Some(c) => {
+              unquoted.push('\\');
+              unquoted.push(c);
+            }
+            None => unquoted.push('\\'),
+          },
+          c => unquoted.push(c),
+        }
+      }
+      unquoted
+    } else {
+      let end = val.match_indices('#').find(|&(i, _)| val[..i].ends_with(char::is_whitespace));
+      val[..end.map_or(val.len(), |(i, _)| i)].trim_end().to_owned()
+    };
+    ret.push((key.to_owned(), val));
+  }
+  Ok(ret)
+}
+
 /// Returns the canonical name of the executable.
 ///
 /// In Windows, this is the file stem only. In Unix, this is the file name.
@@ -172,6 +662,11 @@ pub fn path() -> &'static PathBuf {
 #[allow(clippy::missing_panics_doc)]
 pub fn set<K: AsRef<OsStr>, V: AsRef<OsStr>>(key: K, value: Option<V>) {
   let _guard = env_mutex().lock().unwrap();
+  set_impl(key, value);
+}
+
+/// Sets or removes an environment variable. The caller must hold the lock of [`env_mutex`].
+fn set_impl<K: AsRef<OsStr>, V: AsRef<OsStr>>(key: K, value: Option<V>) {
   match value {
     Some(val) => unsafe {
       env::set_var(key, val);
@@ -182,6 +677,62 @@ pub fn set<K: AsRef<OsStr>, V: AsRef<OsStr>>(key: K, value: Option<V>) {
   }
 }
 
+/// Sets or removes an environment variable, restoring it when the returned guard is dropped.
+///
+/// This works like [`set`], but records the previous value of the variable. See [`ScopedVar`].
+///
+/// # Safety
+///
+/// All environment-variable operations from this module are thread-safe as long as they are used
+/// exclusively.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::env;
+///
+/// {
+///   let _guard = env::set_scoped("MY_VAR", Some("my_value"));
+///   assert_eq!(env::get("MY_VAR"), Some("my_value".into()));
+/// }
+/// assert_eq!(env::get("MY_VAR"), None);
+/// ```
+#[allow(clippy::missing_panics_doc)]
+pub fn set_scoped<K: AsRef<OsStr>, V: AsRef<OsStr>>(key: K, value: Option<V>) -> ScopedVar {
+  let _guard = env_mutex().lock().unwrap();
+  let key = key.as_ref().to_owned();
+  let old_value = env::var_os(&key);
+  set_impl(&key, value);
+  ScopedVar { key, old_value }
+}
+
+/// Takes a snapshot of the whole environment, which restores the environment when dropped. See
+/// [`EnvSnapshot`].
+///
+/// # Safety
+///
+/// All environment-variable operations from this module are thread-safe as long as they are used
+/// exclusively.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::env;
+///
+/// env::set("MY_CHANGED_VAR", Some("old"));
+/// let snapshot = env::snapshot();
+/// env::set("MY_ADDED_VAR", Some("1"));
+/// env::set("MY_CHANGED_VAR", Some("new"));
+/// snapshot.restore();
+/// assert_eq!(env::get("MY_ADDED_VAR"), None);
+/// assert_eq!(env::get("MY_CHANGED_VAR"), Some("old".into()));
+/// ```
+#[allow(clippy::missing_panics_doc)]
+pub fn snapshot() -> EnvSnapshot {
+  let _guard = env_mutex().lock().unwrap();
+  EnvSnapshot { vars: env::vars_os().collect() }
+}
+
 /// Returns the path to the system's configuration directory.
 ///
 /// The returned value depends on the operating system and is either a [`Some`], containing a value from the
@@ -212,6 +763,36 @@ fn system_config_dir_impl() -> Option<PathBuf> {
   None
 }
 
+/// Returns the paths to the system's configuration directories, ordered from highest to lowest priority.
+///
+/// The returned value depends on the operating system:
+///
+/// | Platform | Value
+/// | :------- | :----
+/// | Unix     | The absolute paths from the environment variable `XDG_CONFIG_DIRS`, separated by `:`, if any. Otherwise, the value returned by [`system_config_dir`], if any
+/// | Windows  | The value returned by [`system_config_dir`], if any
+///
+/// Unlike [`system_config_dir`], the function does not check if the directories from `XDG_CONFIG_DIRS`
+/// exist.
+#[must_use]
+pub fn system_config_dirs() -> Vec<PathBuf> { system_config_dirs_impl() }
+
+#[cfg(not(windows))]
+fn system_config_dirs_impl() -> Vec<PathBuf> { xdg_config_dirs(get("XDG_CONFIG_DIRS").as_deref()) }
+
+#[cfg(windows)]
+fn system_config_dirs_impl() -> Vec<PathBuf> { system_config_dir().into_iter().collect() }
+
+/// Returns the absolute paths from `xdg_config_dirs`, falling back to [`system_config_dir`]. Relative paths
+/// are ignored, as required by the XDG Base Directory Specification.
+#[cfg(not(windows))]
+fn xdg_config_dirs(xdg_config_dirs: Option<&OsStr>) -> Vec<PathBuf> {
+  let ret: Vec<_> = xdg_config_dirs
+    .map(|val| env::split_paths(val).filter(|dir| dir.is_absolute()).collect())
+    .unwrap_or_default();
+  if ret.is_empty() { system_config_dir().into_iter().collect() } else { ret }
+}
+
 /// Returns the canonical test name of the executable.
 ///
 /// This is the canonical name as returned by [`name`], stripped of a trailing `-` and 16-digit hexadecimal
@@ -252,14 +833,498 @@ pub fn vars() -> env::VarsOs {
   env::vars_os()
 }
 
+/// Expands references to environment variables in a string, failing on undefined variables.
+///
+/// This works like [`expand`], except that a reference to an undefined variable is an error.
+///
+/// # Safety
+///
+/// All environment-variable operations from this module are thread-safe as long as they are used
+/// exclusively.
+///
+/// # Errors
+///
+/// Returns [`ExpandError::UndefinedVar`] with the name of the first undefined variable referenced.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::env;
+///
+/// env::set("MY_VAR", Some("my_value"));
+/// assert_eq!(env::try_expand("${MY_VAR}.txt")?, "my_value.txt");
+/// assert!(env::try_expand("${MY_UNDEFINED_VAR}.txt").is_err());
+/// # Ok::<(), env::ExpandError>(())
+/// ```
+#[allow(clippy::missing_panics_doc)]
+pub fn try_expand(input: &str) -> Result<Cow<'_, str>, ExpandError> {
+  let _guard = env_mutex().lock().unwrap();
+  let lookup = |name: &str| match env::var_os(name) {
+    Some(val) => Ok(Some(val)),
+    None => Err(ExpandError::UndefinedVar(name.to_owned())),
+  };
+  expand_impl(input, lookup, cfg!(windows))
+}
+
+/// Returns the variable reference at the start of `input`, which starts with `$` or `%`, as a pair of the
+/// variable name and the length of the reference. See [`expand`] for the syntax supported.
+fn var_ref(input: &str) -> Option<(&str, usize)> {
+  if let Some(remaining) = input.strip_prefix("${") {
+    let len = remaining.find('}').filter(|&len| len > 0)?;
+    Some((&remaining[..len], len + 3))
+  } else if let Some(remaining) = input.strip_prefix('$') {
+    let len = remaining.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(remaining.len());
+    let name = &remaining[..len];
+    (!name.is_empty() && !name.starts_with(|c: char| c.is_ascii_digit())).then_some((name, len + 1))
+  } else {
+    let remaining = input.strip_prefix('%')?;
+    let len = remaining.find('%').filter(|&len| len > 0)?;
+    Some((&remaining[..len], len + 2))
+  }
+}
+
+/// Returns the environment variables whose names start with `prefix`.
+///
+/// Like [`vars`], this takes a snapshot of the environment. The prefix is matched against the name of each
+/// variable, converted lossily to a string, so it is case-sensitive. If `strip` is `true`, the prefix is
+/// removed from the names returned.
+///
+/// # Safety
+///
+/// All environment-variable operations from this module are thread-safe as long as they are used
+/// exclusively.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::env;
+///
+/// env::set("MYAPP_PORT", Some("8080"));
+/// for (key, val) in env::vars_with_prefix("MYAPP_", true) {
+///   println!("Setting {key:?} is {val:?}"); // Prints `Setting "PORT" is "8080"`
+/// }
+/// ```
+pub fn vars_with_prefix(prefix: &str, strip: bool) -> impl Iterator<Item = (OsString, OsString)> + use<> {
+  let prefix = prefix.to_owned();
+  vars().filter_map(move |(key, val)| {
+    let key_str = key.to_string_lossy();
+    let remaining = key_str.strip_prefix(&prefix)?;
+    let key = if strip { OsString::from(remaining) } else { key };
+    Some((key, val))
+  })
+}
+
+/// Returns the path of an executable file named `name` found in one of the directories from the
+/// environment variable `PATH`, similar to the Unix command `which`.
+///
+/// This returns the first element of [`which_all`], if any. See there for details.
+///
+/// # Safety
+///
+/// All environment-variable operations from this module are thread-safe as long as they are used
+/// exclusively.
+///
+/// # Examples
+///
+/// ```
+/// use std::ffi::OsStr;
+///
+/// use meadows::env;
+///
+/// if let Some(path) = env::which(OsStr::new("cargo")) {
+///   println!("Cargo is installed at {path:?}");
+/// }
+/// ```
+#[must_use]
+pub fn which(name: &OsStr) -> Option<PathBuf> { which_impl(name, get("PATH").as_deref()).next() }
+
+/// Returns the paths of all executable files named `name` found in the directories from the environment
+/// variable `PATH`, in order.
+///
+/// The directories are searched the way a shell does:
+///
+/// - Empty entries in `PATH` are ignored. Unlike some shells, the function does not treat them as the
+///   current directory.
+/// - If `name` contains a path separator, `PATH` is not searched, and `name` itself is checked.
+/// - On Unix, a file is executable if any of its execute permission bits is set.
+/// - On Windows, each extension from the environment variable `PATHEXT`, e.g. `.EXE`, is appended to `name`
+///   in turn, unless `name` already ends with one of these extensions. If `PATHEXT` is not set,
+///   `.COM;.EXE;.BAT;.CMD` is used.
+///
+/// Each path is canonicalized via [`dunce::canonicalize`]. If `PATH` contains a directory more than once,
+/// the result may contain duplicates.
+///
+/// # Safety
+///
+/// All environment-variable operations from this module are thread-safe as long as they are used
+/// exclusively.
+#[must_use]
+pub fn which_all(name: &OsStr) -> Vec<PathBuf> { which_impl(name, get("PATH").as_deref()).collect() }
+
+fn which_impl(name: &OsStr, path_var: Option<&OsStr>) -> impl Iterator<Item = PathBuf> + use<> {
+  let name = Path::new(name);
+  let dirs: Vec<PathBuf> = if name.as_os_str().is_empty() {
+    Vec::new()
+  } else if name.components().count() > 1 {
+    vec![PathBuf::new()]
+  } else {
+    let dirs = path_var.map(|val| env::split_paths(val).collect::<Vec<_>>()).unwrap_or_default();
+    dirs.into_iter().filter(|dir| !dir.as_os_str().is_empty()).collect()
+  };
+  let file_names = executable_file_names(name.as_os_str());
+
+  dirs
+    .into_iter()
+    .flat_map(move |dir| file_names.iter().map(|file_name| dir.join(file_name)).collect::<Vec<_>>())
+    .filter(|path| is_executable(path))
+    .filter_map(|path| dunce::canonicalize(path).ok())
+}
+
+#[cfg(not(windows))]
+fn executable_file_names(name: &OsStr) -> Vec<OsString> { vec![name.to_owned()] }
+
+#[cfg(windows)]
+fn executable_file_names(name: &OsStr) -> Vec<OsString> {
+  let path_ext = get("PATHEXT").unwrap_or_else(|| OsString::from(".COM;.EXE;.BAT;.CMD"));
+  let path_ext = path_ext.to_string_lossy();
+  let exts: Vec<&str> = path_ext.split(';').filter(|ext| !ext.is_empty()).collect();
+
+  let name_lower = name.to_string_lossy().to_lowercase();
+  if exts.iter().any(|ext| name_lower.ends_with(&ext.to_lowercase())) {
+    return vec![name.to_owned()];
+  }
+  exts
+    .iter()
+    .map(|ext| {
+      let mut file_name = name.to_owned();
+      file_name.push(ext);
+      file_name
+    })
+    .collect()
+}
+
+#[cfg(not(windows))]
+fn is_executable(path: &Path) -> bool {
+  use std::os::unix::fs::PermissionsExt;
+
+  path.metadata().is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(windows)]
+fn is_executable(path: &Path) -> bool { path.is_file() }
+
+/// Sets or removes several environment variables while running `f`, restoring them afterwards.
+///
+/// Each entry of `vars` is a key and a value. If the value is [`None`], the variable is removed. All
+/// variables are set under a single lock, and their previous values are recorded. After `f` returns, or if
+/// it panics, the variables are restored in reverse order (LIFO), again under a single lock. So if a key is
+/// listed more than once, its original value is restored. Only the listed variables are touched, changes
+/// made by `f` to other variables are kept.
+///
+/// # Safety
+///
+/// All environment-variable operations from this module are thread-safe as long as they are used
+/// exclusively.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::env;
+///
+/// let val = env::with_vars(&[("MY_VAR", Some("1")), ("MY_OTHER_VAR", None)], || {
+///   env::get_parsed::<_, i32>("MY_VAR")
+/// });
+/// assert_eq!(val, Some(Ok(1)));
+/// assert_eq!(env::get("MY_VAR"), None);
+/// ```
+#[allow(clippy::missing_panics_doc)]
+pub fn with_vars<R, F: FnOnce() -> R>(vars: &[(&str, Option<&str>)], f: F) -> R {
+  /// Restores the recorded variables when dropped.
+  struct Restore(Vec<(OsString, Option<OsString>)>);
+
+  impl Drop for Restore {
+    fn drop(&mut self) {
+      let _guard = env_mutex().lock().unwrap_or_else(PoisonError::into_inner);
+      for (key, old_value) in self.0.drain(..).rev() {
+        set_impl(key, old_value);
+      }
+    }
+  }
+
+  let _restore = {
+    let _guard = env_mutex().lock().unwrap();
+    let mut old_values = Vec::with_capacity(vars.len());
+    for &(key, value) in vars {
+      old_values.push((OsString::from(key), env::var_os(key)));
+      set_impl(key, value);
+    }
+    Restore(old_values)
+  };
+  f()
+}
+
+/// Returns the XDG base directories.
+///
+/// See [`XdgDirs`] for the sources and fallbacks of the directories on each platform.
+///
+/// # Safety
+///
+/// All environment-variable operations from this module are thread-safe as long as they are used
+/// exclusively.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::env;
+///
+/// let xdg_dirs = env::xdg_dirs();
+/// println!("Configuration files go to {:?}", xdg_dirs.config_home);
+/// ```
+#[must_use]
+pub fn xdg_dirs() -> XdgDirs { xdg_dirs_impl() }
+
+#[cfg(not(windows))]
+fn xdg_dirs_impl() -> XdgDirs {
+  let home_dir = dirs::home_dir();
+  let dir = |key: &str| get(key).map(PathBuf::from).filter(|dir| dir.is_absolute());
+  let dir_or_home = |key: &str, fallback: &str| dir(key).or_else(|| Some(home_dir.as_ref()?.join(fallback)));
+
+  XdgDirs {
+    cache_home: dir_or_home("XDG_CACHE_HOME", ".cache"),
+    config_home: dir_or_home("XDG_CONFIG_HOME", ".config"),
+    data_home: dir_or_home("XDG_DATA_HOME", ".local/share"),
+    runtime_dir: dir("XDG_RUNTIME_DIR"),
+    state_home: dir_or_home("XDG_STATE_HOME", ".local/state"),
+  }
+}
+
+#[cfg(windows)]
+fn xdg_dirs_impl() -> XdgDirs {
+  XdgDirs {
+    cache_home: dirs::cache_dir(),
+    config_home: dirs::config_dir(),
+    data_home: dirs::data_dir(),
+    runtime_dir: None,
+    state_home: dirs::data_local_dir(),
+  }
+}
+
 // Tests ====================================================================================================
 
 #[cfg(test)]
 mod tests {
+  use std::mem;
+
   use super::*;
 
+  // `EnvSnapshot` ------------------------------------------------------------------------------------------
+
+  #[test]
+  fn test_env_snapshot_changes() {
+    let pairs = |pairs: &[(&str, &str)]| -> Vec<(OsString, OsString)> {
+      pairs.iter().map(|&(key, val)| (key.into(), val.into())).collect()
+    };
+    let snapshot = EnvSnapshot { vars: pairs(&[("A", "1"), ("B", "2"), ("C", "3")]).into_iter().collect() };
+
+    assert!(snapshot.changes(pairs(&[("C", "3"), ("A", "1"), ("B", "2")])).is_empty());
+    assert_eq!(snapshot.changes(pairs(&[("A", "1"), ("B", "two"), ("D", "4")])), vec![
+      (OsString::from("B"), Some(OsString::from("2"))),
+      (OsString::from("C"), Some(OsString::from("3"))),
+      (OsString::from("D"), None),
+    ]);
+
+    // Dropping the snapshot must not touch the process environment in this test
+    mem::forget(snapshot);
+  }
+
   // Functions ----------------------------------------------------------------------------------------------
 
+  #[test]
+  fn test_child_env() {
+    let key = "MEADOWS_TEST_CHILD_ENV";
+    let removed_key = vars().next().map(|(key, _)| key).unwrap();
+    let removed_key_str = removed_key.to_string_lossy();
+
+    let env = child_env(&[(key, Some("1")), (&removed_key_str, None), ("MEADOWS_TEST_CHILD_ENV_2", None)]);
+    assert!(env.contains(&(key.into(), "1".into())));
+    assert!(!env.iter().any(|(key, _)| key == &removed_key));
+    assert!(env.is_sorted_by(|lhs, rhs| lhs.0 < rhs.0));
+
+    // The current environment is not modified
+    assert_eq!(get(key), None);
+    assert!(get(&removed_key).is_some());
+  }
+
+  #[test]
+  fn test_color_enabled_impl() {
+    let os = |val: &str| Some(OsString::from(val));
+
+    // No variables set
+    assert!(color_enabled_impl(None, None, None, true));
+    assert!(!color_enabled_impl(None, None, None, false));
+
+    // `NO_COLOR`
+    assert!(!color_enabled_impl(os("1"), None, None, true));
+    assert!(!color_enabled_impl(os("1"), os("1"), os("1"), true));
+    assert!(color_enabled_impl(os(""), None, None, true));
+    assert!(color_enabled_impl(os(""), os("1"), None, false));
+
+    // `CLICOLOR_FORCE`
+    assert!(color_enabled_impl(None, os("1"), None, false));
+    assert!(color_enabled_impl(None, os("1"), os("0"), false));
+    assert!(!color_enabled_impl(None, os("0"), None, false));
+    assert!(color_enabled_impl(None, os("0"), None, true));
+
+    // `CLICOLOR`
+    assert!(!color_enabled_impl(None, None, os("0"), true));
+    assert!(color_enabled_impl(None, None, os("1"), true));
+    assert!(!color_enabled_impl(None, None, os("1"), false));
+  }
+
+  #[test]
+  fn test_expand() -> anyhow::Result<()> {
+    {
+      let _guard = set_scoped("MEADOWS_TEST_ENV_EXPAND", Some("1"));
+      assert_eq!(expand("${MEADOWS_TEST_ENV_EXPAND}-$MEADOWS_TEST_ENV_EXPAND"), "1-1");
+      assert_eq!(try_expand("$$MEADOWS_TEST_ENV_EXPAND")?, "$MEADOWS_TEST_ENV_EXPAND");
+    }
+    assert_eq!(expand("${MEADOWS_TEST_ENV_EXPAND}"), "${MEADOWS_TEST_ENV_EXPAND}");
+    assert!(try_expand("${MEADOWS_TEST_ENV_EXPAND}").is_err());
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_expand_tilde_impl() {
+    let home_dir = || Some(PathBuf::from("/home/alice"));
+    let expand = |path: &str| expand_tilde_impl(Path::new(path), home_dir());
+
+    assert_eq!(expand("~"), Path::new("/home/alice"));
+    assert_eq!(expand("~/"), Path::new("/home/alice"));
+    assert_eq!(expand("~/.config/app"), Path::new("/home/alice/.config/app"));
+
+    // Not expanded
+    for path in ["", "~alice/dir", "dir/~", "dir/~/file", "/~", "a~", "~~"] {
+      assert_eq!(expand(path), Path::new(path));
+    }
+    assert_eq!(expand_tilde_impl(Path::new("~/dir"), None), Path::new("~/dir"));
+  }
+
+  #[test]
+  fn test_get_bool() {
+    let key = "MEADOWS_TEST_GET_BOOL";
+    {
+      let _guard = set_scoped(key, Some("1"));
+      assert_eq!(get_bool(key), Some(true));
+    }
+    assert_eq!(get_bool(key), None);
+  }
+
+  #[test]
+  fn test_get_parsed() {
+    let key = "MEADOWS_TEST_GET_PARSED";
+    {
+      let _guard = set_scoped(key, Some("-12"));
+      assert_eq!(get_parsed::<_, i32>(key), Some(Ok(-12)));
+      assert!(matches!(get_parsed::<_, u32>(key), Some(Err(_))));
+    }
+    assert!(get_parsed::<_, i32>(key).is_none());
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_load_dotenv() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join(".env");
+    fs::write(&path, [
+      "# Comment\n",
+      "MEADOWS_TEST_DOTENV_A=1\n",
+      "export MEADOWS_TEST_DOTENV_B = \"two words\" # Comment\n",
+      "MEADOWS_TEST_DOTENV_C=3\n",
+    ]
+    .concat())?;
+
+    // Restore all variables when done
+    let _guards = [
+      set_scoped("MEADOWS_TEST_DOTENV_A", None::<&str>),
+      set_scoped("MEADOWS_TEST_DOTENV_B", None::<&str>),
+      set_scoped("MEADOWS_TEST_DOTENV_C", Some("old")),
+    ];
+
+    assert_eq!(load_dotenv(&path, false)?, 2);
+    assert_eq!(get("MEADOWS_TEST_DOTENV_A"), Some("1".into()));
+    assert_eq!(get("MEADOWS_TEST_DOTENV_B"), Some("two words".into()));
+    assert_eq!(get("MEADOWS_TEST_DOTENV_C"), Some("old".into()));
+
+    assert_eq!(load_dotenv(&path, true)?, 3);
+    assert_eq!(get("MEADOWS_TEST_DOTENV_C"), Some("3".into()));
+
+    assert!(load_dotenv(dir.path().join("missing.env"), false).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_parse_bool() {
+    for val in ["1", "true", "TRUE", "Yes", "on", " on\n"] {
+      assert_eq!(parse_bool(OsStr::new(val)), Some(true), "{val:?}");
+    }
+    for val in ["0", "false", "False", "NO", "off"] {
+      assert_eq!(parse_bool(OsStr::new(val)), Some(false), "{val:?}");
+    }
+    for val in ["", "2", "y", "n", "truthy"] {
+      assert_eq!(parse_bool(OsStr::new(val)), None, "{val:?}");
+    }
+  }
+
+  #[test]
+  fn test_parse_dotenv() -> io::Result<()> {
+    let text = r#"
+      # Comment
+      A=1
+        export B = two words # Comment
+      C='single # \n quoted' # Comment
+      D="double \"quoted\"\n\t\\ \x" # Comment
+      E=
+      F=a#b
+      G=url=x
+    "#;
+    let pairs: Vec<_> = parse_dotenv(text)?.into_iter().map(|(key, val)| format!("{key}={val}")).collect();
+    assert_eq!(pairs, [
+      "A=1",
+      "B=two words",
+      "C=single # \\n quoted",
+      "D=double \"quoted\"\n\t\\ \\x",
+      "E=",
+      "F=a#b",
+      "G=url=x"
+    ]);
+
+    for text in ["A", "=1", "A B=1", "A='1", "A=\"1"] {
+      let err = parse_dotenv(text).unwrap_err();
+      assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{text:?}");
+    }
+    assert!(parse_dotenv("A=1\nB").unwrap_err().to_string().starts_with("Line 2: "));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_set_scoped() {
+    let key = "MEADOWS_TEST_SET_SCOPED";
+    {
+      let _guard = set_scoped(key, Some("1"));
+      assert_eq!(get(key), Some("1".into()));
+      {
+        let _guard = set_scoped(key, Some("2"));
+        assert_eq!(get(key), Some("2".into()));
+        let _guard = set_scoped(key, None::<&str>);
+        assert_eq!(get(key), None);
+      }
+      assert_eq!(get(key), Some("1".into()));
+    }
+    assert_eq!(get(key), None);
+  }
+
   #[test]
   fn test_test_name_impl() {
     assert_eq!(test_name_impl(OsStr::new("rust_out")), "rust_out");
@@ -273,6 +1338,120 @@ mod tests {
   #[test]
   #[should_panic(expected = "`a-0123456789` is not a valid test-executable name")]
   fn test_test_name_impl_fail_2() { test_name_impl(OsStr::new("a-0123456789")); }
+
+  #[test]
+  fn test_vars_with_prefix() {
+    let _guards = [
+      set_scoped("MEADOWS_TEST_VARS_A", Some("a")),
+      set_scoped("MEADOWS_TEST_VARS_B", Some("b")),
+      set_scoped("MEADOWS_TEST_VAR", Some("c")),
+    ];
+
+    let mut vars: Vec<_> = vars_with_prefix("MEADOWS_TEST_VARS_", false).collect();
+    vars.sort();
+    assert_eq!(vars, vec![
+      (OsString::from("MEADOWS_TEST_VARS_A"), OsString::from("a")),
+      (OsString::from("MEADOWS_TEST_VARS_B"), OsString::from("b")),
+    ]);
+
+    let mut vars: Vec<_> = vars_with_prefix("MEADOWS_TEST_VARS_", true).collect();
+    vars.sort();
+    assert_eq!(vars, vec![
+      (OsString::from("A"), OsString::from("a")),
+      (OsString::from("B"), OsString::from("b")),
+    ]);
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_which() {
+    let name = if cfg!(windows) { "cmd" } else { "sh" };
+    let path = which(OsStr::new(name)).unwrap();
+    assert!(path.is_absolute());
+    assert_eq!(which_all(OsStr::new(name)).first(), Some(&path));
+
+    assert_eq!(which(OsStr::new("meadows-no-such-executable")), None);
+    assert_eq!(which(OsStr::new("")), None);
+  }
+
+  #[cfg(not(windows))]
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_which_impl() -> io::Result<()> {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir()?;
+    let dir_path = dunce::canonicalize(dir.path())?;
+    let dir_1 = dir_path.join("1");
+    let dir_2 = dir_path.join("2");
+    for dir in [&dir_1, &dir_2] {
+      fs::create_dir(dir)?;
+      fs::write(dir.join("tool"), "")?;
+      fs::write(dir.join("data"), "")?;
+    }
+    fs::set_permissions(dir_1.join("tool"), fs::Permissions::from_mode(0o755))?;
+    fs::set_permissions(dir_2.join("tool"), fs::Permissions::from_mode(0o700))?;
+
+    let path_var = env::join_paths([&dir_1, &PathBuf::new(), &dir_2]).unwrap();
+    let which_all = |name: &str| which_impl(OsStr::new(name), Some(&path_var)).collect::<Vec<_>>();
+    assert_eq!(which_all("tool"), vec![dir_1.join("tool"), dir_2.join("tool")]);
+    assert!(which_all("data").is_empty());
+    assert!(which_all("missing").is_empty());
+
+    // Names containing a path separator are not searched for
+    let name = dir_2.join("tool");
+    assert_eq!(which_impl(name.as_os_str(), None).collect::<Vec<_>>(), vec![dir_2.join("tool")]);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_with_vars() {
+    let (key_1, key_2) = ("MEADOWS_TEST_WITH_VARS_1", "MEADOWS_TEST_WITH_VARS_2");
+    let _guard = set_scoped(key_2, Some("old"));
+
+    let ret = with_vars(&[(key_1, Some("1")), (key_2, None), (key_1, Some("2"))], || {
+      assert_eq!(get(key_1), Some("2".into()));
+      assert_eq!(get(key_2), None);
+      42
+    });
+    assert_eq!(ret, 42);
+    assert_eq!(get(key_1), None);
+    assert_eq!(get(key_2), Some("old".into()));
+
+    // Variables are restored on panic as well
+    let result = std::panic::catch_unwind(|| {
+      with_vars(&[(key_1, Some("1")), (key_2, Some("2"))], || panic!("Oops"));
+    });
+    assert!(result.is_err());
+    assert_eq!(get(key_1), None);
+    assert_eq!(get(key_2), Some("old".into()));
+  }
+
+  #[cfg(not(windows))]
+  #[test]
+  fn test_xdg_config_dirs() {
+    let dirs = xdg_config_dirs(Some(OsStr::new("/etc/xdg:relative::/opt/etc")));
+    assert_eq!(dirs, vec![PathBuf::from("/etc/xdg"), PathBuf::from("/opt/etc")]);
+
+    let fallback: Vec<_> = system_config_dir().into_iter().collect();
+    assert_eq!(xdg_config_dirs(None), fallback);
+    assert_eq!(xdg_config_dirs(Some(OsStr::new(""))), fallback);
+  }
+
+  #[cfg(not(windows))]
+  #[test]
+  fn test_xdg_dirs() {
+    let key = "XDG_CONFIG_HOME";
+
+    let _guard = set_scoped(key, Some("/meadows/config"));
+    assert_eq!(xdg_dirs().config_home, Some(PathBuf::from("/meadows/config")));
+
+    // Relative paths are ignored
+    let _guard = set_scoped(key, Some("meadows/config"));
+    assert_eq!(xdg_dirs().config_home, dirs::home_dir().map(|dir| dir.join(".config")));
+  }
 }
 
 // EOF
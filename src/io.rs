@@ -2,10 +2,337 @@
 
 //! I/O-related utilities.
 
+use std::ffi::OsString;
+use std::fs;
 use std::fs::File;
+use std::fs::OpenOptions;
 use std::io;
 use std::io::BufRead;
+use std::io::Read;
+use std::io::Write;
 use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+
+use crate::str::StrExt;
+
+// Constants ------------------------------------------------------------------------------------------------
+
+/// The size of the chunks [`copy_with_progress`] copies, in bytes.
+pub const COPY_CHUNK_SIZE: usize = 64 * 1024;
+
+// `Alignment` ----------------------------------------------------------------------------------------------
+
+/// The alignment of a table column, see [`write_table`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Alignment {
+  /// Cells are left-aligned, i.e. padded on the right.
+  #[default]
+  Left,
+  /// Cells are right-aligned, i.e. padded on the left.
+  Right,
+}
+
+// `FileLock` -----------------------------------------------------------------------------------------------
+
+/// An exclusive advisory lock on a file, released when dropped.
+///
+/// The lock is taken with [`File::lock`], i.e. `flock` on Unix and `LockFileEx` on Windows. It is held per
+/// open file, so two [`FileLock`]s for the same path exclude each other even within the same process.
+///
+/// Locks are advisory: They only exclude processes that take the lock as well. A process that does not use
+/// [`FileLock`] can still read and write the lock file, or the file the lock is meant to protect.
+///
+/// The lock file is created if it does not exist, and it is not removed when the lock is released, as
+/// removing it would race with other processes that have already opened it. Its contents are not modified.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+///
+/// use meadows::io;
+/// use meadows::io::FileLock;
+///
+/// let _lock = FileLock::acquire(Path::new("state.toml.lock"))?;
+/// io::write_atomic(Path::new("state.toml"), b"count = 42\n")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+#[must_use = "the lock is released immediately if it is not used"]
+pub struct FileLock {
+  file: File,
+  path: PathBuf,
+}
+
+impl FileLock {
+  /// Acquires an exclusive lock on the file at `path`, blocking until it is available.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Err`] if the lock file cannot be opened or locked.
+  pub fn acquire(path: &Path) -> io::Result<Self> {
+    let file = Self::open(path)?;
+    file.lock()?;
+    Ok(Self { file, path: path.to_owned() })
+  }
+
+  fn open(path: &Path) -> io::Result<File> {
+    OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)
+  }
+
+  /// Returns the path of the lock file.
+  #[must_use]
+  pub fn path(&self) -> &Path { &self.path }
+
+  /// Tries to acquire an exclusive lock on the file at `path` without blocking.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Err`] with [`io::ErrorKind::WouldBlock`] if the lock is held by another [`FileLock`] or
+  /// process. Returns [`Err`] with a different kind if the lock file cannot be opened or locked.
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use std::io;
+  /// use std::path::Path;
+  ///
+  /// use meadows::io::FileLock;
+  ///
+  /// match FileLock::try_acquire(Path::new("app.lock")) {
+  ///   Ok(_lock) => println!("Running"),
+  ///   Err(err) if err.kind() == io::ErrorKind::WouldBlock => println!("Already running"),
+  ///   Err(err) => return Err(err),
+  /// }
+  /// # Ok::<(), io::Error>(())
+  /// ```
+  pub fn try_acquire(path: &Path) -> io::Result<Self> {
+    let file = Self::open(path)?;
+    file.try_lock()?;
+    Ok(Self { file, path: path.to_owned() })
+  }
+}
+
+impl Drop for FileLock {
+  fn drop(&mut self) { let _ = self.file.unlock(); }
+}
+
+// `FlushOnDrop` --------------------------------------------------------------------------------------------
+
+/// A writer that flushes the wrapped writer when dropped, see [`locked_stdout`].
+struct FlushOnDrop<W: Write>(W);
+
+impl<W: Write> Drop for FlushOnDrop<W> {
+  fn drop(&mut self) { let _ = self.0.flush(); }
+}
+
+impl<W: Write> Write for FlushOnDrop<W> {
+  #[inline]
+  fn flush(&mut self) -> io::Result<()> { self.0.flush() }
+
+  #[inline]
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.write(buf) }
+}
+
+// `TableOptions` -------------------------------------------------------------------------------------------
+
+/// Options for [`write_table`].
+#[derive(Clone, Debug)]
+pub struct TableOptions {
+  /// The alignment of each column. Columns without an alignment are left-aligned.
+  pub alignments: Vec<Alignment>,
+  /// If this is a [`Some`], a row made up of the given [`char`] is written after the first row, which is
+  /// the header row.
+  pub header_separator: Option<char>,
+  /// If this is a [`Some`], each line is truncated to the given number of display columns.
+  pub max_width: Option<usize>,
+  /// The separator written between two columns.
+  pub separator: String,
+}
+
+impl Default for TableOptions {
+  /// Returns the default [`TableOptions`].
+  ///
+  /// | Field              | Default Value
+  /// | :----------------- | :------------
+  /// | `alignments`       | Empty, i.e. all columns are left-aligned
+  /// | `header_separator` | [`None`]
+  /// | `max_width`        | [`None`]
+  /// | `separator`        | Two spaces
+  fn default() -> Self {
+    Self { alignments: Vec::new(), header_separator: None, max_width: None, separator: String::from("  ") }
+  }
+}
+
+// Functions ------------------------------------------------------------------------------------------------
+
+/// Appends lines to a file.
+///
+/// Works like [`write_lines`], but appends to the file instead of truncating it. If the file does not exist,
+/// it is created.
+///
+/// # Errors
+///
+/// See [`OpenOptions::open`] and [`Write::write_all`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use meadows::io;
+///
+/// io::append_lines("history.txt", ["ls -l"])?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn append_lines<P, I, S>(path: P, lines: I) -> io::Result<()>
+where
+  P: AsRef<Path>,
+  I: IntoIterator<Item = S>,
+  S: AsRef<str>, {
+  let file = OpenOptions::new().append(true).create(true).open(path)?;
+  write_lines_impl(file, lines)
+}
+
+/// Copies the entire contents of a reader into a writer, reporting progress.
+///
+/// Works like [`io::copy`], but the data is copied in chunks of at most [`COPY_CHUNK_SIZE`] bytes, and
+/// `on_progress` is called after each chunk has been written, with the total number of bytes copied so far.
+/// Hence, the last call receives the total number of bytes copied, which is returned as well. If the reader
+/// is empty, `on_progress` is not called.
+///
+/// Reads that fail with [`io::ErrorKind::Interrupted`] are retried.
+///
+/// # Errors
+///
+/// Returns [`Err`] if reading from `reader` or writing to `writer` fails.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::io;
+///
+/// let mut reader: &[u8] = b"Hello, world!";
+/// let mut writer = Vec::new();
+/// let total = io::copy_with_progress(&mut reader, &mut writer, |count| eprintln!("{count} bytes copied"))?;
+/// assert_eq!(total, 13);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn copy_with_progress<R, W, F>(reader: &mut R, writer: &mut W, mut on_progress: F) -> io::Result<u64>
+where
+  R: Read + ?Sized,
+  W: Write + ?Sized,
+  F: FnMut(u64), {
+  let mut buf = vec![0; COPY_CHUNK_SIZE];
+  let mut ret = 0;
+  loop {
+    let len = match reader.read(&mut buf) {
+      Ok(0) => return Ok(ret),
+      Ok(len) => len,
+      Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+      Err(err) => return Err(err),
+    };
+    writer.write_all(&buf[..len])?;
+    ret += len as u64;
+    on_progress(ret);
+  }
+}
+
+fn create_temp_file(path: &Path) -> io::Result<(PathBuf, File)> {
+  static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+  let name = path.file_name().ok_or_else(|| {
+    io::Error::new(io::ErrorKind::InvalidInput, format!("Path {path:?} does not name a file"))
+  })?;
+  loop {
+    let mut tmp_name = OsString::from(".");
+    tmp_name.push(name);
+    tmp_name.push(format!(".{}.{}.tmp", process::id(), COUNTER.fetch_add(1, Ordering::Relaxed)));
+    let tmp_path = parent_dir(path).join(tmp_name);
+    match OpenOptions::new().write(true).create_new(true).open(&tmp_path) {
+      Ok(file) => return Ok((tmp_path, file)),
+      Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+      Err(err) => return Err(err),
+    }
+  }
+}
+
+/// Returns a locked, ANSI-aware writer for `stderr` that is flushed when dropped.
+///
+/// This is the `stderr` counterpart of [`locked_stdout`], see there for details.
+#[must_use]
+pub fn locked_stderr() -> impl Write { FlushOnDrop(stderr().lock()) }
+
+/// Returns a locked, ANSI-aware writer for `stdout` that is flushed when dropped.
+///
+/// The writer holds the lock of the stream returned by [`stdout`], so output from other threads is not
+/// interleaved with its own. Unlike a plain lock, it is flushed when it goes out of scope, including during
+/// unwinding, so output written before a panic is not lost.
+///
+/// Note that [`process::exit`] does not run destructors, so the writer is not flushed if the process exits
+/// while the writer is alive. Call [`Write::flush`] explicitly before exiting, or drop the writer first.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::prelude::*;
+///
+/// use meadows::io;
+///
+/// let mut stdout = io::locked_stdout();
+/// writeln!(stdout, "Hello, world!")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[must_use]
+pub fn locked_stdout() -> impl Write { FlushOnDrop(stdout().lock()) }
+
+fn parent_dir(path: &Path) -> &Path {
+  match path.parent() {
+    Some(dir) if !dir.as_os_str().is_empty() => dir,
+    _ => Path::new("."),
+  }
+}
+
+/// Reads the first line of a file.
+///
+/// The function stops reading after the first line terminator. The terminator (`\n` or `\r\n`) and a
+/// leading byte-order mark (BOM), if any, are stripped. If the file is empty, [`None`] is returned.
+///
+/// # Errors
+///
+/// See [`File::open`] and [`BufRead::read_line`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use meadows::io;
+///
+/// if let Some(version) = io::read_first_line("VERSION")? {
+///   println!("Version: {version}");
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn read_first_line<P>(path: P) -> io::Result<Option<String>>
+where
+  P: AsRef<Path>, {
+  let file = File::open(path)?;
+  let mut ret = String::new();
+  if io::BufReader::new(file).read_line(&mut ret)? == 0 {
+    return Ok(None);
+  }
+
+  if ret.ends_with('\n') {
+    ret.pop();
+    if ret.ends_with('\r') {
+      ret.pop();
+    }
+  }
+  if ret.starts_with('\u{feff}') {
+    ret.remove(0);
+  }
+  Ok(Some(ret))
+}
 
 /// Reads lines from a file.
 ///
@@ -19,6 +346,118 @@ where
   Ok(io::BufReader::new(file).lines())
 }
 
+/// Reads all lines from a file into a [`Vec`].
+///
+/// Unlike [`read_lines`], which returns a lazy iterator, this function reads the whole file at once. It uses
+/// the file size as a hint to allocate the buffer up front. Invalid UTF-8 sequences are replaced with
+/// [`U+FFFD REPLACEMENT CHARACTER`](char::REPLACEMENT_CHARACTER), and line terminators (`\n` or `\r\n`) are
+/// stripped.
+///
+/// # Errors
+///
+/// See [`File::open`] and [`io::Read::read_to_end`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use meadows::io;
+///
+/// for line in io::read_lines_vec("names.txt")? {
+///   println!("{line}");
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn read_lines_vec<P>(path: P) -> io::Result<Vec<String>>
+where
+  P: AsRef<Path>, {
+  let mut file = File::open(path)?;
+  let size_hint = file.metadata().map_or(0, |metadata| metadata.len());
+  let mut buf = Vec::with_capacity(usize::try_from(size_hint).unwrap_or(0));
+  file.read_to_end(&mut buf)?;
+
+  let text = String::from_utf8_lossy(&buf);
+  Ok(text.lines().map(String::from).collect())
+}
+
+/// Reads lines from `stdin`.
+///
+/// This is the `stdin` counterpart of [`read_lines`], e.g. for filter programs that read lines, transform
+/// them, and write them to [`stdout`]. `stdin` is locked as long as the returned iterator is alive. Line
+/// terminators (`\n` or `\r\n`) are stripped.
+///
+/// If `stdin` is empty or closed, the iterator ends immediately, i.e. it yields no lines at all. If a line
+/// is not valid UTF-8, an [`Err`] with [`io::ErrorKind::InvalidData`] is yielded.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::prelude::*;
+///
+/// use meadows::io;
+///
+/// let mut stdout = io::stdout().lock();
+/// for line in io::read_stdin_lines() {
+///   writeln!(stdout, "{}", line?.to_uppercase())?;
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[must_use]
+pub fn read_stdin_lines() -> io::Lines<io::StdinLock<'static>> { io::stdin().lines() }
+
+/// Reads the contents of a file into a [`String`].
+///
+/// Unlike [`fs::read_to_string`], the returned error names the offending path, so there is no need to add
+/// context at the call site. The underlying [`io::Error`] is the source of the returned error.
+///
+/// # Errors
+///
+/// Returns [`Err`] if [`fs::read_to_string`] fails.
+///
+/// # Examples
+///
+/// ```no_run
+/// use meadows::io;
+///
+/// let text = io::read_to_string("notes.txt")?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn read_to_string<P>(path: P) -> anyhow::Result<String>
+where
+  P: AsRef<Path>, {
+  let path = path.as_ref();
+  fs::read_to_string(path)
+    .map_err(|err| anyhow::Error::from(err).context(format!("Cannot read file {path:?}")))
+}
+
+#[cfg(not(windows))]
+fn replace_file(from: &Path, to: &Path) -> io::Result<()> {
+  fs::rename(from, to)?;
+  #[cfg(unix)]
+  if let Ok(dir) = File::open(parent_dir(to)) {
+    // Make the rename itself durable. This is best-effort, as not all file systems support it
+    let _ = dir.sync_all();
+  }
+  Ok(())
+}
+
+#[cfg(windows)]
+#[allow(clippy::permissions_set_readonly_false)]
+fn replace_file(from: &Path, to: &Path) -> io::Result<()> {
+  // `fs::rename` replaces an existing file on Windows, unless that file is read-only
+  match fs::rename(from, to) {
+    Err(err) if err.kind() == io::ErrorKind::PermissionDenied => {
+      let mut permissions = fs::metadata(to)?.permissions();
+      if !permissions.readonly() {
+        return Err(err);
+      }
+      permissions.set_readonly(false);
+      fs::set_permissions(to, permissions)?;
+      fs::rename(from, to)
+    }
+    result => result,
+  }
+}
+
 /// Returns a configured ANSI-aware stream for `stderr`.
 ///
 /// See [`anstream::stderr`].
@@ -26,6 +465,14 @@ where
 #[must_use]
 pub fn stderr() -> anstream::Stderr { anstream::stderr() }
 
+/// Returns a handle to `stdin`.
+///
+/// Unlike [`stdout`] and [`stderr`], this is the plain [`io::Stdin`], as no ANSI processing is applied to
+/// input. It is offered for symmetry, see also [`read_stdin_lines`].
+#[inline]
+#[must_use]
+pub fn stdin() -> io::Stdin { io::stdin() }
+
 /// Returns a configured ANSI-aware stream for `stdout`.
 ///
 /// See [`anstream::stdout`].
@@ -33,4 +480,441 @@ pub fn stderr() -> anstream::Stderr { anstream::stderr() }
 #[must_use]
 pub fn stdout() -> anstream::Stdout { anstream::stdout() }
 
+/// Writes rows of cells as a table with aligned columns.
+///
+/// The width of each column is the width of its widest cell, measured in [`char`]s. Cells are padded with
+/// spaces according to the column's alignment, and columns are separated by `opts.separator`. Rows may have
+/// different numbers of cells, missing cells are treated as empty. Trailing whitespace is removed from each
+/// line.
+///
+/// If `opts.header_separator` is a [`Some`], the first row is treated as a header row and followed by a row
+/// made up of the separator [`char`], each column as wide as the column itself.
+///
+/// Cells are measured in display columns, see [`display_width`](StrExt::display_width), so wide characters
+/// such as CJK ideographs are accounted for. If `opts.max_width` is a [`Some`], each line is truncated to
+/// that number of display columns, so the rightmost columns may be cut off.
+///
+/// # Errors
+///
+/// Returns [`Err`] if writing to `w` fails.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::io::Alignment;
+/// use meadows::io::TableOptions;
+///
+/// let rows = vec![
+///   vec![String::from("Name"), String::from("Size")],
+///   vec![String::from("a.txt"), String::from("12")],
+///   vec![String::from("bb.txt"), String::from("3456")],
+/// ];
+/// let opts = TableOptions {
+///   alignments: vec![Alignment::Left, Alignment::Right],
+///   header_separator: Some('-'),
+///   ..TableOptions::default()
+/// };
+///
+/// let mut buf = Vec::new();
+/// meadows::io::write_table(&mut buf, &rows, &opts)?;
+/// assert_eq!(String::from_utf8_lossy(&buf), "\
+/// Name    Size
+/// ------  ----
+/// a.txt     12
+/// bb.txt  3456
+/// ");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_table<W: Write>(w: &mut W, rows: &[Vec<String>], opts: &TableOptions) -> io::Result<()> {
+  let col_count = rows.iter().map(Vec::len).max().unwrap_or(0);
+  let mut widths = vec![0; col_count];
+  for row in rows {
+    for (width, cell) in widths.iter_mut().zip(row) {
+      *width = (*width).max(cell.display_width());
+    }
+  }
+
+  let write_line = |w: &mut W, line: &str| -> io::Result<()> {
+    let line = match opts.max_width {
+      Some(max_width) => line.split_at_width(max_width).0,
+      None => line,
+    };
+    writeln!(w, "{}", line.trim_end())
+  };
+
+  for (i, row) in rows.iter().enumerate() {
+    let mut line = String::new();
+    for (j, &width) in widths.iter().enumerate() {
+      if j > 0 {
+        line.push_str(&opts.separator);
+      }
+      let cell = row.get(j).map_or("", String::as_str);
+      line.push_str(&match opts.alignments.get(j).copied().unwrap_or_default() {
+        Alignment::Left => cell.pad_right(width, ' '),
+        Alignment::Right => cell.pad_left(width, ' '),
+      });
+    }
+    write_line(w, &line)?;
+
+    if i == 0 && let Some(c) = opts.header_separator {
+      let cells: Vec<String> = widths.iter().map(|&width| "".pad_right(width, c)).collect();
+      write_line(w, &cells.join(&opts.separator))?;
+    }
+  }
+
+  Ok(())
+}
+
+/// Writes a file atomically.
+///
+/// The contents are written to a temporary file in the same directory as `path`, which is then flushed to
+/// disk and renamed to `path`. Hence, a reader sees either the old file or the complete new file, never a
+/// partial one, even if the process crashes while writing. If anything fails, the temporary file is removed.
+///
+/// If `path` already exists, its permissions are copied to the new file. Replacing an existing file works on
+/// all platforms; on Windows, this includes read-only files.
+///
+/// # Errors
+///
+/// Returns [`Err`] if `path` does not name a file, or if creating, writing, syncing, or renaming the
+/// temporary file fails.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+///
+/// use meadows::io;
+///
+/// io::write_atomic(Path::new("state.toml"), b"count = 42\n")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+  write_atomic_impl(path, |file| file.write_all(contents))
+}
+
+fn write_atomic_impl<F>(path: &Path, write: F) -> io::Result<()>
+where
+  F: FnOnce(&mut File) -> io::Result<()>, {
+  let (tmp_path, mut file) = create_temp_file(path)?;
+  let result = write(&mut file)
+    .and_then(|()| match fs::metadata(path) {
+      Ok(metadata) => file.set_permissions(metadata.permissions()),
+      Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+      Err(err) => Err(err),
+    })
+    .and_then(|()| file.sync_all());
+  drop(file);
+
+  let result = result.and_then(|()| replace_file(&tmp_path, path));
+  if result.is_err() {
+    let _ = fs::remove_file(&tmp_path);
+  }
+  result
+}
+
+/// Writes lines to a file.
+///
+/// The file is created if it does not exist, and truncated if it does. Each line is followed by `\n` on all
+/// platforms, never by `\r\n`. Hence, lines written by this function are read back unchanged by
+/// [`read_lines`] and [`read_lines_vec`], as long as they do not contain line terminators themselves.
+///
+/// # Errors
+///
+/// See [`File::create`] and [`Write::write_all`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use meadows::io;
+///
+/// io::write_lines("names.txt", ["Alice", "Bob"])?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_lines<P, I, S>(path: P, lines: I) -> io::Result<()>
+where
+  P: AsRef<Path>,
+  I: IntoIterator<Item = S>,
+  S: AsRef<str>, {
+  write_lines_impl(File::create(path)?, lines)
+}
+
+fn write_lines_impl<I, S>(file: File, lines: I) -> io::Result<()>
+where
+  I: IntoIterator<Item = S>,
+  S: AsRef<str>, {
+  let mut w = io::BufWriter::new(file);
+  for line in lines {
+    w.write_all(line.as_ref().as_bytes())?;
+    w.write_all(b"\n")?;
+  }
+  w.flush()
+}
+
+// Tests ====================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+
+  use super::*;
+
+  // `FileLock` ---------------------------------------------------------------------------------------------
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_file_lock() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("file.lock");
+
+    let lock = FileLock::acquire(&path)?;
+    assert_eq!(lock.path(), path);
+    assert!(path.is_file());
+    assert_eq!(FileLock::try_acquire(&path).unwrap_err().kind(), io::ErrorKind::WouldBlock);
+
+    drop(lock);
+    let lock = FileLock::try_acquire(&path)?;
+    assert_eq!(FileLock::try_acquire(&path).unwrap_err().kind(), io::ErrorKind::WouldBlock);
+    drop(lock);
+
+    assert!(path.is_file());
+    let _lock = FileLock::acquire(&path)?;
+
+    Ok(())
+  }
+
+  // `FlushOnDrop` ------------------------------------------------------------------------------------------
+
+  #[test]
+  fn test_flush_on_drop() -> io::Result<()> {
+    let mut buf = io::BufWriter::new(Vec::new());
+    {
+      let mut w = FlushOnDrop(&mut buf);
+      write!(w, "Hello")?;
+      assert!(w.0.get_ref().is_empty());
+    }
+    assert_eq!(buf.get_ref(), b"Hello");
+
+    Ok(())
+  }
+
+  // Functions ----------------------------------------------------------------------------------------------
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_append_lines() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("file.txt");
+
+    append_lines(&path, ["1st line"])?;
+    append_lines(&path, [String::from("2nd line"), String::from("3rd line")])?;
+    append_lines(&path, Vec::<&str>::new())?;
+    assert_eq!(fs::read_to_string(&path)?, "1st line\n2nd line\n3rd line\n");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_copy_with_progress() -> io::Result<()> {
+    let data: Vec<u8> = (0..=u8::MAX).cycle().take(2 * COPY_CHUNK_SIZE + 100).collect();
+    let mut writer = Vec::new();
+    let mut counts = Vec::new();
+    let total = copy_with_progress(&mut data.as_slice(), &mut writer, |count| counts.push(count))?;
+    assert_eq!(total, data.len() as u64);
+    assert_eq!(writer, data);
+    assert_eq!(counts.last(), Some(&total));
+    assert!(counts.is_sorted());
+
+    let mut counts = Vec::new();
+    assert_eq!(copy_with_progress(&mut io::empty(), &mut io::sink(), |count| counts.push(count))?, 0);
+    assert!(counts.is_empty());
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_read_first_line() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("file.txt");
+
+    fs::write(&path, "1.2.3\n")?;
+    assert_eq!(read_first_line(&path)?, Some(String::from("1.2.3")));
+
+    fs::write(&path, "\u{feff}1st line\r\n2nd line\n3rd line")?;
+    assert_eq!(read_first_line(&path)?, Some(String::from("1st line")));
+
+    fs::write(&path, "no terminator")?;
+    assert_eq!(read_first_line(&path)?, Some(String::from("no terminator")));
+
+    fs::write(&path, "\n")?;
+    assert_eq!(read_first_line(&path)?, Some(String::new()));
+
+    fs::write(&path, "")?;
+    assert_eq!(read_first_line(&path)?, None);
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_read_lines_vec() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("file.txt");
+
+    fs::write(&path, b"1st line\r\n2nd line\n\nGr\xc3\xbc\xc3e\n")?;
+    assert_eq!(read_lines_vec(&path)?, ["1st line", "2nd line", "", "Grü\u{fffd}e"]);
+
+    fs::write(&path, "no terminator")?;
+    assert_eq!(read_lines_vec(&path)?, ["no terminator"]);
+
+    fs::write(&path, "")?;
+    assert!(read_lines_vec(&path)?.is_empty());
+
+    assert!(read_lines_vec(dir.path().join("missing.txt")).is_err());
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_read_to_string() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("file.txt");
+
+    fs::write(&path, "Grüße\n")?;
+    assert_eq!(read_to_string(&path)?, "Grüße\n");
+
+    let path = dir.path().join("missing.txt");
+    let err = read_to_string(&path).unwrap_err();
+    assert_eq!(err.to_string(), format!("Cannot read file {path:?}"));
+    assert_eq!(err.downcast_ref::<io::Error>().map(io::Error::kind), Some(io::ErrorKind::NotFound));
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_write_atomic() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("file.txt");
+    let file_names = || -> io::Result<Vec<OsString>> {
+      fs::read_dir(dir.path())?.map(|entry| entry.map(|entry| entry.file_name())).collect()
+    };
+
+    write_atomic(&path, b"1st")?;
+    assert_eq!(fs::read(&path)?, b"1st");
+    write_atomic(&path, b"2nd")?;
+    assert_eq!(fs::read(&path)?, b"2nd");
+    assert_eq!(file_names()?, ["file.txt"]);
+
+    // Simulate a write error
+
+    let err = write_atomic_impl(&path, |file| {
+      file.write_all(b"partial")?;
+      Err(io::Error::other("Simulated"))
+    })
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Simulated");
+    assert_eq!(fs::read(&path)?, b"2nd");
+    assert_eq!(file_names()?, ["file.txt"]);
+
+    assert_eq!(write_atomic(&dir.path().join(".."), b"").unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+    Ok(())
+  }
+
+  #[cfg(unix)]
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_write_atomic_permissions() -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("file.txt");
+
+    fs::write(&path, "old")?;
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o640))?;
+    write_atomic(&path, b"new")?;
+    assert_eq!(fs::read(&path)?, b"new");
+    assert_eq!(fs::metadata(&path)?.permissions().mode() & 0o777, 0o640);
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_write_lines() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("file.txt");
+
+    let lines = ["1st line", "", "Grüße", "  indented  "];
+    write_lines(&path, lines)?;
+    assert_eq!(fs::read_to_string(&path)?, "1st line\n\nGrüße\n  indented  \n");
+    assert_eq!(read_lines(&path)?.collect::<io::Result<Vec<_>>>()?, lines);
+    assert_eq!(read_lines_vec(&path)?, lines);
+
+    write_lines(&path, ["new"])?;
+    assert_eq!(fs::read_to_string(&path)?, "new\n");
+
+    write_lines(&path, Vec::<String>::new())?;
+    assert_eq!(fs::read_to_string(&path)?, "");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_write_table() -> io::Result<()> {
+    let rows = [vec!["Name", "Größe", "Note"], vec!["äöü", "1"], vec!["x", "12345", "ok"]];
+    let rows: Vec<Vec<String>> = rows
+      .into_iter()
+      .map(|row| row.into_iter().map(String::from).collect())
+      .collect();
+
+    let mut buf = Vec::new();
+    let opts = TableOptions {
+      alignments: vec![Alignment::Left, Alignment::Right],
+      header_separator: Some('='),
+      separator: String::from(" | "),
+      ..TableOptions::default()
+    };
+    write_table(&mut buf, &rows, &opts)?;
+    assert_eq!(String::from_utf8_lossy(&buf), [
+      "Name | Größe | Note\n",
+      "==== | ===== | ====\n",
+      "äöü  |     1 |\n",
+      "x    | 12345 | ok\n",
+    ]
+    .concat());
+
+    let mut buf = Vec::new();
+    write_table(&mut buf, &rows, &TableOptions { max_width: Some(9), ..opts })?;
+    assert_eq!(String::from_utf8_lossy(&buf), "Name | Gr\n==== | ==\näöü  |\nx    | 12\n");
+
+    // Wide cells take up two columns per `char`
+    let rows = vec![
+      vec![String::from("日本"), String::from("x")],
+      vec![String::from("a"), String::from("bb")],
+    ];
+    let opts = TableOptions {
+      header_separator: Some('-'),
+      separator: String::from(" | "),
+      ..TableOptions::default()
+    };
+    let mut buf = Vec::new();
+    write_table(&mut buf, &rows, &opts)?;
+    assert_eq!(String::from_utf8_lossy(&buf), "日本 | x\n---- | --\na    | bb\n");
+
+    let mut buf = Vec::new();
+    write_table(&mut buf, &rows, &TableOptions { max_width: Some(3), ..opts })?;
+    assert_eq!(String::from_utf8_lossy(&buf), "日\n---\na\n");
+
+    let mut buf = Vec::new();
+    write_table(&mut buf, &[], &TableOptions::default())?;
+    assert!(buf.is_empty());
+
+    Ok(())
+  }
+}
+
 // EOF
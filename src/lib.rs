@@ -8,8 +8,23 @@
 //!
 //! ## Crate Features
 //!
+//! - **`ctrlc`** (disabled by default) - When enabled, `crate::process::install_shutdown_handler` is
+//!   available to handle Ctrl-C and termination signals.
+//! - **`indexmap`** (disabled by default) - When enabled, [`Uvec`](crate::collections::Uvec) can be
+//!   converted to and from `indexmap::IndexSet`.
+//! - **`notify`** (disabled by default) - When enabled, `crate::config::watch` is available to watch
+//!   configuration files for changes.
+//! - **`serde`** (disabled by default) - When enabled, [`Uvec`](crate::collections::Uvec) implements
+//!   `Serialize` and `Deserialize`.
+//! - **`terminal_size`** (disabled by default) - When enabled, [`text_width`] takes the width of the
+//!   terminal into account, as determined by the `terminal_size` crate.
+//! - **`toml`** (disabled by default) - When enabled, `crate::config::merge_toml` is available to merge
+//!   TOML configuration files.
 //! - **`tracing_config`** (disabled by default) - When enabled, the `crate::tracing::config` module is
 //!   available.
+//! - **`unicode_width`** (disabled by default) - When enabled, display widths, e.g. for
+//!   [`StrExt::display_width`](crate::str::StrExt::display_width), are taken from the `unicode-width` crate
+//!   instead of a built-in approximation.
 //!
 //! ## Logging
 //!
@@ -45,9 +60,78 @@ pub mod vec;
 ///
 /// This may be the assumed minimum column width of a terminal or editor, including line breaks. Lines may be
 /// wrapped if they exceed `TEXT_WIDTH` - 1 columns.
+///
+/// To take the actual terminal width into account, use [`text_width`].
 pub const TEXT_WIDTH: usize = 110;
 
 /// The crate version.
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// Functions ------------------------------------------------------------------------------------------------
+
+/// Returns a formatting hint like [`TEXT_WIDTH`], taking the environment and the terminal into account.
+///
+/// The value is resolved in the following order:
+///
+/// 1. If the environment variable `COLUMNS` is set to a positive integer, its value is returned.
+/// 2. If the `terminal_size` feature is enabled, `stdout` is a terminal, and its width can be determined,
+///    the number of columns is returned.
+/// 3. Otherwise, [`TEXT_WIDTH`] is returned.
+///
+/// The value is not cached, so a resized terminal is taken into account on the next call.
+///
+/// # Examples
+///
+/// ```
+/// let width = meadows::text_width();
+/// assert!(width > 0);
+/// ```
+#[must_use]
+pub fn text_width() -> usize {
+  if let Some(columns) = env::get("COLUMNS").and_then(|val| val.to_str()?.trim().parse::<usize>().ok())
+    && columns > 0
+  {
+    return columns;
+  }
+  terminal_width().unwrap_or(TEXT_WIDTH)
+}
+
+/// Returns the width of the terminal `stdout` is connected to, if any.
+#[cfg(feature = "terminal_size")]
+fn terminal_width() -> Option<usize> {
+  let (terminal_size::Width(width), _) = terminal_size::terminal_size_of(std::io::stdout())?;
+  (width > 0).then_some(usize::from(width))
+}
+
+/// Returns the width of the terminal `stdout` is connected to, if any.
+#[cfg(not(feature = "terminal_size"))]
+fn terminal_width() -> Option<usize> { None }
+
+// Tests ====================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use std::io::IsTerminal;
+
+  use super::*;
+
+  // Functions ----------------------------------------------------------------------------------------------
+
+  #[test]
+  fn test_text_width() {
+    {
+      let _guard = env::set_scoped("COLUMNS", Some("72"));
+      assert_eq!(text_width(), 72);
+    }
+    for val in ["0", "wide", ""] {
+      let _guard = env::set_scoped("COLUMNS", Some(val));
+      let width = text_width();
+      assert!(width > 0);
+      if !std::io::stdout().is_terminal() {
+        assert_eq!(width, TEXT_WIDTH);
+      }
+    }
+  }
+}
+
 // EOF
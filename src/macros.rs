@@ -3,9 +3,72 @@
 //! Macros.
 
 pub use owo_colors::OwoColorize as Colorize;
+pub use owo_colors::Style;
 
 // Macros ---------------------------------------------------------------------------------------------------
 
+/// Prints the process invocation name, an error label, and a message to a stream, flushes the stream, and
+/// exits the process with the given exit code.
+///
+/// The message is written just like [`process_error`](crate::process_error) does, then
+/// [`std::process::exit`] is called with `code`. Errors from writing or flushing are ignored, as the process
+/// exits anyway.
+///
+/// This macro never returns. As [`std::process::exit`] does not unwind the stack, no destructors run,
+/// neither for values on the current stack nor for any guards being held, e.g. the `ArcMutexGuard` returned
+/// by `meadows::tracing::config::init`. Drop such guards explicitly before invoking the macro, if required.
+/// If the caller must stay in control, e.g. in tests, use [`try_bail_process`](crate::try_bail_process)
+/// instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[macro_use] extern crate meadows;
+/// fn run() -> anyhow::Result<()> {
+///   anyhow::bail!("Engine stalled")
+/// }
+///
+/// if let Err(err) = run() {
+///   bail_process!(meadows::io::stderr(), 1, "{err:#}"); // -> "${inv_name}: error: Engine stalled\n"
+/// }
+/// ```
+#[macro_export]
+macro_rules! bail_process {
+  ($stream:expr, $code:expr, $($arg:tt)+) => {{
+    let code: i32 = $code;
+    let _ = $crate::try_bail_process!($stream, code, $($arg)+);
+    ::std::process::exit(code)
+  }};
+}
+
+/// Prints the process invocation name, a debug label, and a message to a stream, if debug output is
+/// enabled.
+///
+/// Debug output is enabled if the environment variable `MEADOWS_DEBUG` is set to a true value, see
+/// [`is_debug_enabled`](crate::macros::is_debug_enabled). The variable is checked on each invocation.
+/// Otherwise, nothing is printed, and the message arguments are not evaluated.
+///
+/// The macro evaluates to a [`std::io::Result<()>`], just like [`writeln`] does.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate meadows;
+/// let mut stderr = meadows::io::stderr().lock();
+/// process_debug!(stderr, "Engine state: {}", "idle")?; // -> "${inv_name}: debug: Engine state: idle\n"
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[macro_export]
+macro_rules! process_debug {
+  ($stream:expr, $($arg:tt)+) => {{
+    if $crate::macros::is_debug_enabled() {
+      $crate::process_msg!($stream, "debug", $crate::macros::Style::new().bold().dimmed(), $($arg)+)
+    } else {
+      ::std::io::Result::Ok(())
+    }
+  }};
+}
+
 /// Prints the process invocation name, an error label, and a message to a stream.
 ///
 /// The macro evaluates to a [`std::io::Result<()>`], just like [`writeln`] does.
@@ -20,16 +83,75 @@ pub use owo_colors::OwoColorize as Colorize;
 /// ```
 #[macro_export]
 macro_rules! process_error {
-  ($stream:expr, $($arg:tt)+) => {{
+  ($stream:expr, $($arg:tt)+) => {
+    $crate::process_msg!($stream, "error", $crate::macros::Style::new().bold().red(), $($arg)+)
+  };
+}
+
+/// Returns the process invocation name, an error label, and a message as a [`String`].
+///
+/// This is the string form of [`process_error`](crate::process_error), e.g. to be passed to
+/// [`anyhow::Context`] or to a `tracing` event. No line terminator is appended.
+///
+/// As there is no target stream, the label is styled as if the message were written to `stderr`: Color is
+/// used if [`crate::env::color_enabled`] returns `true` for `stderr`, which applies the same rules as
+/// [`crate::io::stderr`]. Otherwise, the returned [`String`] contains no ANSI escape codes.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate meadows;
+/// let msg = process_error_string!("Cannot start engine"); // -> "${inv_name}: error: Cannot start engine"
+/// assert!(msg.ends_with("Cannot start engine"));
+/// ```
+#[macro_export]
+macro_rules! process_error_string {
+  ($($arg:tt)+) => {{
+    use $crate::macros::Colorize;
+
+    let name = $crate::env::inv_name().to_string_lossy();
+    let label = if $crate::env::color_enabled(::std::io::IsTerminal::is_terminal(&::std::io::stderr())) {
+      "error".bold().red().to_string()
+    } else {
+      ::std::string::String::from("error")
+    };
+    ::std::format!("{}: {}: {}", name, label, format_args!($($arg)+))
+  }};
+}
+
+/// Prints the process invocation name, a custom label, and a message to a stream.
+///
+/// `label` may be any value that implements [`Display`](std::fmt::Display). It is styled with `style`, an
+/// [`owo_colors::Style`], which is re-exported as [`Style`](crate::macros::Style).
+/// [`process_error`](crate::process_error), [`process_note`](crate::process_note),
+/// [`process_warn`](crate::process_warn), and [`process_debug`](crate::process_debug) are built on top of
+/// this macro.
+///
+/// The macro evaluates to a [`std::io::Result<()>`], just like [`writeln`] does.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate meadows;
+/// use meadows::macros::Style;
+///
+/// let mut stderr = meadows::io::stderr().lock();
+/// let style = Style::new().bold().cyan();
+/// process_msg!(stderr, "hint", style, "Try `--force`")?; // -> "${inv_name}: hint: Try `--force`\n"
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[macro_export]
+macro_rules! process_msg {
+  ($stream:expr, $label:expr, $style:expr, $($arg:tt)+) => {{
     use ::std::io::prelude::*;
     use $crate::macros::Colorize;
 
     let name = $crate::env::inv_name().to_string_lossy();
-    writeln!($stream, "{}: {}: {}", name, "error".bold().red(), format_args!($($arg)+))
+    writeln!($stream, "{}: {}: {}", name, ($label).style($style), format_args!($($arg)+))
   }};
 }
 
-/// Prints the process invocation name and a message to , a note label, and a message to a stream.
+/// Prints the process invocation name, a note label, and a message to a stream.
 ///
 /// The macro evaluates to a [`std::io::Result<()>`], just like [`writeln`] does.
 ///
@@ -43,12 +165,39 @@ macro_rules! process_error {
 /// ```
 #[macro_export]
 macro_rules! process_note {
-  ($stream:expr, $($arg:tt)+) => {{
-    use ::std::io::prelude::*;
+  ($stream:expr, $($arg:tt)+) => {
+    $crate::process_msg!($stream, "note", $crate::macros::Style::new().bold().green(), $($arg)+)
+  };
+}
+
+/// Returns the process invocation name, a note label, and a message as a [`String`].
+///
+/// This is the string form of [`process_note`](crate::process_note), e.g. to be passed to
+/// [`anyhow::Context`] or to a `tracing` event. No line terminator is appended.
+///
+/// As there is no target stream, the label is styled as if the message were written to `stdout`: Color is
+/// used if [`crate::env::color_enabled`] returns `true` for `stdout`, which applies the same rules as
+/// [`crate::io::stdout`]. Otherwise, the returned [`String`] contains no ANSI escape codes.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate meadows;
+/// let msg = process_note_string!("Engine started"); // -> "${inv_name}: note: Engine started"
+/// assert!(msg.ends_with("Engine started"));
+/// ```
+#[macro_export]
+macro_rules! process_note_string {
+  ($($arg:tt)+) => {{
     use $crate::macros::Colorize;
 
     let name = $crate::env::inv_name().to_string_lossy();
-    writeln!($stream, "{}: {}: {}", name, "note".bold().green(), format_args!($($arg)+))
+    let label = if $crate::env::color_enabled(::std::io::IsTerminal::is_terminal(&::std::io::stdout())) {
+      "note".bold().green().to_string()
+    } else {
+      ::std::string::String::from("note")
+    };
+    ::std::format!("{}: {}: {}", name, label, format_args!($($arg)+))
   }};
 }
 
@@ -66,13 +215,77 @@ macro_rules! process_note {
 /// ```
 #[macro_export]
 macro_rules! process_warn {
-  ($stream:expr, $($arg:tt)+) => {{
-    use ::std::io::prelude::*;
+  ($stream:expr, $($arg:tt)+) => {
+    $crate::process_msg!($stream, "warning", $crate::macros::Style::new().bold().yellow(), $($arg)+)
+  };
+}
+
+/// Returns the process invocation name, a warning label, and a message as a [`String`].
+///
+/// This is the string form of [`process_warn`](crate::process_warn), e.g. to be passed to
+/// [`anyhow::Context`] or to a `tracing` event. No line terminator is appended.
+///
+/// As there is no target stream, the label is styled as if the message were written to `stderr`: Color is
+/// used if [`crate::env::color_enabled`] returns `true` for `stderr`, which applies the same rules as
+/// [`crate::io::stderr`]. Otherwise, the returned [`String`] contains no ANSI escape codes.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate meadows;
+/// let msg = process_warn_string!("Engine at {}%", 95); // -> "${inv_name}: warning: Engine at 95%"
+/// assert!(msg.ends_with("Engine at 95%"));
+/// ```
+#[macro_export]
+macro_rules! process_warn_string {
+  ($($arg:tt)+) => {{
     use $crate::macros::Colorize;
 
     let name = $crate::env::inv_name().to_string_lossy();
-    writeln!($stream, "{}: {}: {}", name, "warning".bold().yellow(), format_args!($($arg)+))
+    let label = if $crate::env::color_enabled(::std::io::IsTerminal::is_terminal(&::std::io::stderr())) {
+      "warning".bold().yellow().to_string()
+    } else {
+      ::std::string::String::from("warning")
+    };
+    ::std::format!("{}: {}: {}", name, label, format_args!($($arg)+))
   }};
 }
 
+/// Prints the process invocation name, an error label, and a message to a stream, flushes the stream, and
+/// returns the given exit code.
+///
+/// This is the non-diverging companion of [`bail_process`](crate::bail_process): It writes and flushes just
+/// like [`bail_process`](crate::bail_process), but instead of exiting the process, it evaluates to a
+/// [`std::io::Result<i32>`] that holds `code`. This leaves it to the caller to exit, e.g. after dropping
+/// guards, and makes the output testable.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate meadows;
+/// let mut buf = Vec::new();
+/// let code = try_bail_process!(buf, 2, "Engine stalled")?;
+/// assert_eq!(code, 2);
+/// assert!(String::from_utf8_lossy(&buf).ends_with("Engine stalled\n"));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[macro_export]
+macro_rules! try_bail_process {
+  ($stream:expr, $code:expr, $($arg:tt)+) => {{
+    let stream = &mut $stream;
+    $crate::process_error!(stream, $($arg)+)
+      .and_then(|()| ::std::io::Write::flush(stream))
+      .map(|()| -> i32 { $code })
+  }};
+}
+
+// Functions ------------------------------------------------------------------------------------------------
+
+/// Checks if [`process_debug`] prints messages.
+///
+/// This is the case if the environment variable `MEADOWS_DEBUG` is set to a true value as accepted by
+/// [`crate::env::get_bool`].
+#[must_use]
+pub fn is_debug_enabled() -> bool { crate::env::get_bool("MEADOWS_DEBUG").unwrap_or(false) }
+
 // EOF
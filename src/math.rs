@@ -2,10 +2,14 @@
 
 #![allow(clippy::doc_markdown)]
 
-//! Math module.
+//! Math-related utilities.
 //!
-//! There's not much we can do right now except test inline math such as $\sum_{i=1}^{n} i =
-//! \frac{n(n+1)}{2}$.
+//! Generic integer functions such as [`gcd`], [`lcm`], [`div_floor`], and [`round_up_to_multiple`] work for
+//! all primitive integer types, see [`Integer`]. Simple statistics such as [`mean`], [`median`],
+//! [`variance`], and [`std_dev`] are computed over slices of [`f64`]. [`approx_eq`], [`clamp`], and
+//! [`map_range`] are small helpers for [`f64`] values.
+//!
+//! The module documentation also tests inline math such as $\sum_{i=1}^{n} i = \frac{n(n+1)}{2}$.
 //!
 //! Testing $\KaTeX$, not to be confused with $\LaTeX$. Here are some examples:
 //!
@@ -23,7 +27,679 @@
 //! {1 + \frac{q^2}{(1-q)}+\frac{q^6}{(1-q)(1-q^2)}+\cdots} =
 //! \prod_{j=0}^{\infty}\frac{1}{(1-q^{5j+2})(1-q^{5j+3})}, \quad\quad \text{for }\lvert q\rvert<1. $$
 
-/// Some math function.
-pub fn some_math_function() {}
+use std::fmt::Debug;
+use std::ops::Add;
+use std::ops::Div;
+use std::ops::Rem;
+use std::ops::Sub;
+
+// `Integer` ------------------------------------------------------------------------------------------------
+
+mod private {
+  pub trait Sealed {}
+}
+
+/// A primitive integer type.
+///
+/// This trait is implemented for all signed and unsigned primitive integer types. It is sealed, i.e. it
+/// cannot be implemented outside of this crate.
+pub trait Integer:
+  Copy
+  + Debug
+  + Ord
+  + Add<Output = Self>
+  + Div<Output = Self>
+  + Rem<Output = Self>
+  + Sub<Output = Self>
+  + private::Sealed {
+  /// The value `1`.
+  const ONE: Self;
+
+  /// The value `0`.
+  const ZERO: Self;
+
+  /// Computes the absolute value, returning [`None`] if it overflows. For unsigned types, this is always a
+  /// [`Some`].
+  fn checked_abs(self) -> Option<Self>;
+
+  /// Computes `self * rhs`, returning [`None`] if it overflows.
+  fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+  /// Computes `self % rhs`, returning [`None`] if `rhs` is `0` or the division overflows.
+  fn checked_rem(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+  (signed: $($ty:ty),*) => {$(
+    impl private::Sealed for $ty {}
+
+    impl Integer for $ty {
+      const ONE: Self = 1;
+      const ZERO: Self = 0;
+
+      fn checked_abs(self) -> Option<Self> { self.checked_abs() }
+
+      fn checked_mul(self, rhs: Self) -> Option<Self> { self.checked_mul(rhs) }
+
+      fn checked_rem(self, rhs: Self) -> Option<Self> { self.checked_rem(rhs) }
+    }
+  )*};
+  (unsigned: $($ty:ty),*) => {$(
+    impl private::Sealed for $ty {}
+
+    impl Integer for $ty {
+      const ONE: Self = 1;
+      const ZERO: Self = 0;
+
+      fn checked_abs(self) -> Option<Self> { Some(self) }
+
+      fn checked_mul(self, rhs: Self) -> Option<Self> { self.checked_mul(rhs) }
+
+      fn checked_rem(self, rhs: Self) -> Option<Self> { self.checked_rem(rhs) }
+    }
+  )*};
+}
+
+impl_integer!(signed: i8, i16, i32, i64, i128, isize);
+impl_integer!(unsigned: u8, u16, u32, u64, u128, usize);
+
+// `VarianceKind` -------------------------------------------------------------------------------------------
+
+/// Whether [`variance`] and [`std_dev`] treat the values as a population or as a sample.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum VarianceKind {
+  /// The values are the whole population. The sum of squared deviations is divided by $n$.
+  #[default]
+  Population,
+  /// The values are a sample of a larger population. The sum of squared deviations is divided by $n - 1$
+  /// (Bessel's correction).
+  Sample,
+}
+
+// Functions ------------------------------------------------------------------------------------------------
+
+/// Checks if two values are approximately equal.
+///
+/// The values are considered equal if their absolute difference is at most `epsilon`, or if they are equal,
+/// which also covers equal infinities. NaN is never approximately equal to anything.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::math;
+///
+/// assert!(math::approx_eq(0.1 + 0.2, 0.3, 1e-9));
+/// assert!(!math::approx_eq(1.0, 1.1, 1e-9));
+/// ```
+#[allow(clippy::float_cmp)]
+#[must_use]
+pub fn approx_eq(a: f64, b: f64, epsilon: f64) -> bool { a == b || (a - b).abs() <= epsilon }
+
+/// Computes the greatest common divisor of two integers, returning [`None`] if it overflows.
+///
+/// See [`gcd`]. The result overflows only for signed types, if both `a` and `b` are multiples of `T::MIN`,
+/// e.g. for `checked_gcd(i32::MIN, 0)`, as `-i32::MIN` is not representable.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::math;
+///
+/// assert_eq!(math::checked_gcd(-12, 18), Some(6));
+/// assert_eq!(math::checked_gcd(i32::MIN, 0), None);
+/// ```
+pub fn checked_gcd<T: Integer>(a: T, b: T) -> Option<T> {
+  let (mut a, mut b) = (a, b);
+  while b != T::ZERO {
+    // The remainder only overflows for `T::MIN % -1`, which is `0`
+    let rem = a.checked_rem(b).unwrap_or(T::ZERO);
+    a = b;
+    b = rem;
+  }
+  a.checked_abs()
+}
+
+/// Computes the least common multiple of two integers, returning [`None`] if it overflows.
+///
+/// See [`lcm`].
+///
+/// # Examples
+///
+/// ```
+/// use meadows::math;
+///
+/// assert_eq!(math::checked_lcm(4_u8, 6), Some(12));
+/// assert_eq!(math::checked_lcm(200_u8, 3), None);
+/// ```
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+  if a == T::ZERO || b == T::ZERO {
+    return Some(T::ZERO);
+  }
+  let gcd = checked_gcd(a, b)?;
+  (a / gcd).checked_mul(b)?.checked_abs()
+}
+
+/// Restricts a value to an interval.
+///
+/// Returns `lo` if `val` is less than `lo`, `hi` if `val` is greater than `hi`, and `val` otherwise. Unlike
+/// [`f64::clamp`], this function does not panic: If `lo` is greater than `hi`, the bounds are swapped, so
+/// the interval may be given in either order. If `val` is NaN, NaN is returned. A NaN bound is ignored.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::math;
+///
+/// assert_eq!(math::clamp(1.5, 0.0, 1.0), 1.0);
+/// assert_eq!(math::clamp(-0.5, 1.0, 0.0), 0.0);
+/// ```
+#[must_use]
+pub fn clamp(val: f64, lo: f64, hi: f64) -> f64 {
+  let (lo, hi) = if lo > hi { (hi, lo) } else { (lo, hi) };
+  if val.is_nan() { val } else { val.max(lo).min(hi) }
+}
+
+/// Divides two integers, rounding the quotient towards positive infinity.
+///
+/// Unlike the `/` operator, which rounds towards zero, this rounds up for positive quotients as well, e.g.
+/// `div_ceil(7, 2)` is `4`, whereas `7 / 2` is `3`. For negative quotients, the result is the same as for
+/// `/`.
+///
+/// # Panics
+///
+/// Panics if `b` is `0`, or if the division overflows, i.e. for `T::MIN` and `-1`, just like the `/`
+/// operator does.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::math;
+///
+/// assert_eq!(math::div_ceil(7, 2), 4);
+/// assert_eq!(math::div_ceil(-7, 2), -3);
+/// assert_eq!(math::div_ceil(7, -2), -3);
+/// assert_eq!(math::div_ceil(-7, -2), 4);
+/// ```
+#[must_use]
+pub fn div_ceil<T: Integer>(a: T, b: T) -> T {
+  let quot = a / b;
+  let rem = a % b;
+  // The quotient was rounded down if it is positive, i.e. if the remainder has the sign of `b`
+  if rem != T::ZERO && (rem > T::ZERO) == (b > T::ZERO) { quot + T::ONE } else { quot }
+}
+
+/// Divides two integers, rounding the quotient towards negative infinity.
+///
+/// Unlike the `/` operator, which rounds towards zero, this rounds down for negative quotients as well, e.g.
+/// `div_floor(-7, 2)` is `-4`, whereas `-7 / 2` is `-3`. For unsigned types, this is the same as `/`.
+///
+/// # Panics
+///
+/// Panics if `b` is `0`, or if the division overflows, i.e. for `T::MIN` and `-1`, just like the `/`
+/// operator does.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::math;
+///
+/// assert_eq!(math::div_floor(7, 2), 3);
+/// assert_eq!(math::div_floor(-7, 2), -4);
+/// assert_eq!(math::div_floor(7, -2), -4);
+/// assert_eq!(math::div_floor(-7, -2), 3);
+/// ```
+#[must_use]
+pub fn div_floor<T: Integer>(a: T, b: T) -> T {
+  let quot = a / b;
+  let rem = a % b;
+  // The quotient was rounded up if it is negative, i.e. if the remainder does not have the sign of `b`
+  if rem != T::ZERO && (rem > T::ZERO) != (b > T::ZERO) { quot - T::ONE } else { quot }
+}
+
+/// Computes the greatest common divisor of two integers.
+///
+/// The function uses the Euclidean algorithm. The result is never negative. `gcd(a, 0)` is `|a|`, so
+/// `gcd(0, 0)` is `0`.
+///
+/// # Panics
+///
+/// Panics if the result overflows, see [`checked_gcd`].
+///
+/// # Examples
+///
+/// ```
+/// use meadows::math;
+///
+/// assert_eq!(math::gcd(12_u32, 18), 6);
+/// assert_eq!(math::gcd(-12, 18), 6);
+/// assert_eq!(math::gcd(0, 0), 0);
+/// ```
+#[must_use]
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+  checked_gcd(a, b).unwrap_or_else(|| panic!("Greatest common divisor of {a:?} and {b:?} overflows"))
+}
+
+/// Computes the least common multiple of two integers.
+///
+/// The result is never negative. If `a` or `b` is `0`, the result is `0`.
+///
+/// # Panics
+///
+/// Panics if the result overflows. Use [`checked_lcm`] to handle overflow.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::math;
+///
+/// assert_eq!(math::lcm(4_u32, 6), 12);
+/// assert_eq!(math::lcm(-4, 6), 12);
+/// assert_eq!(math::lcm(0, 6), 0);
+/// ```
+#[must_use]
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+  checked_lcm(a, b).unwrap_or_else(|| panic!("Least common multiple of {a:?} and {b:?} overflows"))
+}
+
+/// Maps a value linearly from an input range to an output range.
+///
+/// `in_lo` is mapped to `out_lo`, and `in_hi` is mapped to `out_hi`. Either range may be descending. The
+/// result is not clamped, i.e. a value outside of the input range is mapped to a value outside of the output
+/// range, use [`clamp`] if required. If the input range is empty, i.e. `in_lo` equals `in_hi`, `out_lo` is
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::math;
+///
+/// // Map a progress of 0..1 to a percentage
+/// assert_eq!(math::map_range(0.5, 0.0, 1.0, 0.0, 100.0), 50.0);
+/// assert_eq!(math::map_range(0.25, 0.0, 1.0, 100.0, 0.0), 75.0);
+/// ```
+#[allow(clippy::float_cmp)]
+#[must_use]
+pub fn map_range(val: f64, in_lo: f64, in_hi: f64, out_lo: f64, out_hi: f64) -> f64 {
+  if in_lo == in_hi {
+    return out_lo;
+  }
+  out_lo + (val - in_lo) * (out_hi - out_lo) / (in_hi - in_lo)
+}
+
+/// Computes the arithmetic mean of a slice of values.
+///
+/// Returns [`None`] if `values` is empty. NaN values are not skipped but propagate, i.e. if `values`
+/// contains NaN, the result is NaN.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::math;
+///
+/// assert_eq!(math::mean(&[1.0, 2.0, 6.0]), Some(3.0));
+/// assert_eq!(math::mean(&[]), None);
+/// ```
+#[allow(clippy::cast_precision_loss)] // Slices are never long enough to lose precision
+#[must_use]
+pub fn mean(values: &[f64]) -> Option<f64> {
+  if values.is_empty() {
+    return None;
+  }
+  Some(values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Computes the median of a slice of values, sorting the slice in place.
+///
+/// Returns [`None`] if `values` is empty. If the number of values is even, the median is the mean of the two
+/// middle values. The slice is sorted with [`f64::total_cmp`]. NaN values are not skipped but propagate,
+/// i.e. if `values` contains NaN, the result is NaN.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::math;
+///
+/// assert_eq!(math::median(&mut [3.0, 1.0, 2.0]), Some(2.0));
+/// assert_eq!(math::median(&mut [4.0, 1.0, 3.0, 2.0]), Some(2.5));
+/// assert_eq!(math::median(&mut []), None);
+/// ```
+#[must_use]
+pub fn median(values: &mut [f64]) -> Option<f64> {
+  if values.is_empty() {
+    return None;
+  }
+  values.sort_unstable_by(f64::total_cmp);
+  if values.iter().any(|val| val.is_nan()) {
+    return Some(f64::NAN);
+  }
+
+  let mid = values.len() / 2;
+  if values.len().is_multiple_of(2) {
+    Some(f64::midpoint(values[mid - 1], values[mid]))
+  } else {
+    Some(values[mid])
+  }
+}
+
+/// Rounds an integer down to a multiple of another integer.
+///
+/// The result is the greatest multiple of `m` that is less than or equal to `n`. The sign of `m` does not
+/// matter, as `m` and `-m` have the same multiples.
+///
+/// # Panics
+///
+/// Panics if `m` is `0`, or if the result overflows, e.g. for `round_down_to_multiple(-128_i8, 3)`.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::math;
+///
+/// assert_eq!(math::round_down_to_multiple(17_usize, 8), 16);
+/// assert_eq!(math::round_down_to_multiple(-17, 8), -24);
+/// ```
+#[must_use]
+pub fn round_down_to_multiple<T: Integer>(n: T, m: T) -> T {
+  round_to_multiple(n, m, div_floor)
+    .unwrap_or_else(|| panic!("Rounding {n:?} down to a multiple of {m:?} overflows"))
+}
+
+fn round_to_multiple<T, F>(n: T, m: T, div: F) -> Option<T>
+where
+  T: Integer,
+  F: FnOnce(T, T) -> T, {
+  assert!(m != T::ZERO, "Cannot round {n:?} to a multiple of 0");
+  let m = m.checked_abs()?;
+  div(n, m).checked_mul(m)
+}
+
+/// Rounds an integer up to a multiple of another integer.
+///
+/// The result is the smallest multiple of `m` that is greater than or equal to `n`. The sign of `m` does not
+/// matter, as `m` and `-m` have the same multiples.
+///
+/// # Panics
+///
+/// Panics if `m` is `0`, or if the result overflows, e.g. for `round_up_to_multiple(255_u8, 2)`.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::math;
+///
+/// assert_eq!(math::round_up_to_multiple(17_usize, 8), 24);
+/// assert_eq!(math::round_up_to_multiple(-17, 8), -16);
+/// ```
+#[must_use]
+pub fn round_up_to_multiple<T: Integer>(n: T, m: T) -> T {
+  round_to_multiple(n, m, div_ceil)
+    .unwrap_or_else(|| panic!("Rounding {n:?} up to a multiple of {m:?} overflows"))
+}
+
+/// Computes the standard deviation of a slice of values.
+///
+/// This is the square root of the [`variance`], see there.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::math;
+/// use meadows::math::VarianceKind;
+///
+/// let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+/// assert_eq!(math::std_dev(&values, VarianceKind::Population), Some(2.0));
+/// ```
+#[must_use]
+pub fn std_dev(values: &[f64], kind: VarianceKind) -> Option<f64> { variance(values, kind).map(f64::sqrt) }
+
+/// Computes the variance of a slice of values.
+///
+/// `kind` tells whether `values` are the whole population or a sample, see [`VarianceKind`]. Returns
+/// [`None`] if `values` is empty, or, for a sample, if it contains less than two values. NaN values are not
+/// skipped but propagate, i.e. if `values` contains NaN, the result is NaN.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::math;
+/// use meadows::math::VarianceKind;
+///
+/// let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+/// assert_eq!(math::variance(&values, VarianceKind::Population), Some(4.0));
+/// assert_eq!(math::variance(&values, VarianceKind::Sample), Some(32.0 / 7.0));
+/// ```
+#[allow(clippy::cast_precision_loss)] // Slices are never long enough to lose precision
+#[must_use]
+pub fn variance(values: &[f64], kind: VarianceKind) -> Option<f64> {
+  let divisor = match kind {
+    VarianceKind::Population => values.len(),
+    VarianceKind::Sample => values.len().checked_sub(1)?,
+  };
+  if divisor == 0 {
+    return None;
+  }
+
+  let mean = mean(values)?;
+  let sum: f64 = values.iter().map(|val| (val - mean).powi(2)).sum();
+  Some(sum / divisor as f64)
+}
+
+// Tests ====================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Benchmark timings with a mean of 15, a median of 15, and a sum of squared deviations of 180.
+  const TIMINGS: [f64; 6] = [20.0, 8.0, 10.0, 22.0, 11.0, 19.0];
+
+  // Functions ----------------------------------------------------------------------------------------------
+
+  #[test]
+  fn test_approx_eq() {
+    assert!(approx_eq(1.0, 1.0, 0.0));
+    assert!(approx_eq(0.1 + 0.2, 0.3, f64::EPSILON));
+    assert!(!approx_eq(0.1 + 0.2, 0.3, 0.0));
+    assert!(approx_eq(1.0, 1.5, 0.5));
+    assert!(approx_eq(-1.0, -1.5, 0.5));
+    assert!(!approx_eq(1.0, 1.6, 0.5));
+    assert!(approx_eq(f64::INFINITY, f64::INFINITY, 0.0));
+    assert!(!approx_eq(f64::INFINITY, f64::NEG_INFINITY, f64::MAX));
+    assert!(!approx_eq(f64::NAN, f64::NAN, f64::INFINITY));
+  }
+
+  #[test]
+  fn test_checked_gcd() {
+    assert_eq!(checked_gcd(i8::MIN, 0), None);
+    assert_eq!(checked_gcd(i8::MIN, i8::MIN), None);
+    assert_eq!(checked_gcd(i8::MIN, -1), Some(1));
+    assert_eq!(checked_gcd(i8::MIN, 64), Some(64));
+    assert_eq!(checked_gcd(u8::MAX, 0), Some(u8::MAX));
+  }
+
+  #[test]
+  fn test_checked_lcm() {
+    assert_eq!(checked_lcm(16_u8, 15), Some(240));
+    assert_eq!(checked_lcm(16_u8, 17), None);
+    assert_eq!(checked_lcm(i8::MIN, 1), None);
+    assert_eq!(checked_lcm(i8::MIN, 0), Some(0));
+    assert_eq!(checked_lcm(-64_i8, 2), Some(64));
+    assert_eq!(checked_lcm(u64::MAX, u64::MAX - 1), None);
+  }
+
+  #[allow(clippy::float_cmp)]
+  #[test]
+  fn test_clamp() {
+    assert_eq!(clamp(0.5, 0.0, 1.0), 0.5);
+    assert_eq!(clamp(-0.5, 0.0, 1.0), 0.0);
+    assert_eq!(clamp(1.5, 0.0, 1.0), 1.0);
+    assert_eq!(clamp(1.5, 1.0, 0.0), 1.0);
+    assert_eq!(clamp(-0.5, 1.0, 0.0), 0.0);
+    assert_eq!(clamp(1.5, 0.0, f64::NAN), 1.5);
+    assert_eq!(clamp(-1.5, f64::NAN, 1.0), -1.5);
+    assert!(clamp(f64::NAN, 0.0, 1.0).is_nan());
+  }
+
+  #[test]
+  fn test_div_ceil() {
+    assert_eq!(div_ceil(6, 2), 3);
+    assert_eq!(div_ceil(7, 2), 4);
+    assert_eq!(div_ceil(-7, 2), -3);
+    assert_eq!(div_ceil(7, -2), -3);
+    assert_eq!(div_ceil(-7, -2), 4);
+    assert_eq!(div_ceil(-6, -2), 3);
+    assert_eq!(div_ceil(0, -2), 0);
+    assert_eq!(div_ceil(1_u8, 255), 1);
+    assert_eq!(div_ceil(u8::MAX, 2), 128);
+    assert_eq!(div_ceil(i8::MIN, 3), -42);
+    assert_eq!(div_ceil(i8::MIN, 1), i8::MIN);
+    assert_eq!(div_ceil(i8::MAX, -1), -i8::MAX);
+  }
+
+  #[test]
+  fn test_div_floor() {
+    assert_eq!(div_floor(6, 2), 3);
+    assert_eq!(div_floor(7, 2), 3);
+    assert_eq!(div_floor(-7, 2), -4);
+    assert_eq!(div_floor(7, -2), -4);
+    assert_eq!(div_floor(-7, -2), 3);
+    assert_eq!(div_floor(-6, -2), 3);
+    assert_eq!(div_floor(0, -2), 0);
+    assert_eq!(div_floor(254_u8, 255), 0);
+    assert_eq!(div_floor(i8::MIN, 3), -43);
+    assert_eq!(div_floor(i8::MAX, -2), -64);
+    assert_eq!(div_floor(i8::MIN, 1), i8::MIN);
+  }
+
+  #[test]
+  #[should_panic(expected = "attempt to divide by zero")]
+  fn test_div_floor_zero() { let _ = div_floor(1, 0); }
+
+  #[test]
+  fn test_gcd() {
+    assert_eq!(gcd(0_u32, 0), 0);
+    assert_eq!(gcd(0_u32, 7), 7);
+    assert_eq!(gcd(7_u32, 0), 7);
+    assert_eq!(gcd(48_u32, 18), 6);
+    assert_eq!(gcd(18_u32, 48), 6);
+    assert_eq!(gcd(-48, 18), 6);
+    assert_eq!(gcd(48, -18), 6);
+    assert_eq!(gcd(-48, -18), 6);
+    assert_eq!(gcd(-7, 0), 7);
+
+    // Large coprime pairs
+    assert_eq!(gcd(u64::MAX, u64::MAX - 1), 1);
+    assert_eq!(gcd(1_000_000_007_u64, 998_244_353), 1);
+    assert_eq!(gcd(i128::MAX, i128::MAX - 1), 1);
+    assert_eq!(gcd(2_u128.pow(100), 3_u128.pow(60)), 1);
+    assert_eq!(gcd(2_u128.pow(100), 6_u128.pow(40)), 2_u128.pow(40));
+  }
+
+  #[test]
+  #[should_panic(expected = "Greatest common divisor of -2147483648 and 0 overflows")]
+  fn test_gcd_overflow() { let _ = gcd(i32::MIN, 0); }
+
+  #[test]
+  fn test_lcm() {
+    assert_eq!(lcm(0_u32, 0), 0);
+    assert_eq!(lcm(0_u32, 7), 0);
+    assert_eq!(lcm(4_u32, 6), 12);
+    assert_eq!(lcm(-4, 6), 12);
+    assert_eq!(lcm(-4, -6), 12);
+    assert_eq!(lcm(21_usize, 6), 42);
+    assert_eq!(lcm(1_000_000_007_u64, 998_244_353), 998_244_359_987_710_471);
+  }
+
+  #[test]
+  #[should_panic(expected = "Least common multiple of 255 and 254 overflows")]
+  fn test_lcm_overflow() { let _ = lcm(u8::MAX, 254); }
+
+  #[test]
+  fn test_map_range() {
+    assert!(approx_eq(map_range(0.5, 0.0, 1.0, 0.0, 100.0), 50.0, 1e-12));
+    assert!(approx_eq(map_range(0.0, 0.0, 1.0, 0.0, 100.0), 0.0, 1e-12));
+    assert!(approx_eq(map_range(1.0, 0.0, 1.0, 0.0, 100.0), 100.0, 1e-12));
+    assert!(approx_eq(map_range(2.0, 0.0, 1.0, 0.0, 100.0), 200.0, 1e-12));
+    assert!(approx_eq(map_range(15.0, 10.0, 20.0, -1.0, 1.0), 0.0, 1e-12));
+    assert!(approx_eq(map_range(15.0, 20.0, 10.0, 0.0, 10.0), 5.0, 1e-12));
+    assert!(approx_eq(map_range(12.5, 10.0, 20.0, 10.0, 0.0), 7.5, 1e-12));
+    assert!(approx_eq(map_range(3.0, 1.0, 1.0, 4.0, 8.0), 4.0, 1e-12));
+  }
+
+  #[test]
+  fn test_mean() {
+    assert_eq!(mean(&TIMINGS), Some(15.0));
+    assert_eq!(mean(&[-1.5]), Some(-1.5));
+    assert_eq!(mean(&[]), None);
+    assert!(mean(&[1.0, f64::NAN]).is_some_and(f64::is_nan));
+  }
+
+  #[test]
+  fn test_median() {
+    let mut values = TIMINGS;
+    assert_eq!(median(&mut values), Some(15.0));
+    assert!(values.is_sorted());
+
+    assert_eq!(median(&mut [5.0, -1.0, 3.0]), Some(3.0));
+    assert_eq!(median(&mut [-1.5]), Some(-1.5));
+    assert_eq!(median(&mut []), None);
+    assert!(median(&mut [1.0, f64::NAN, 2.0]).is_some_and(f64::is_nan));
+  }
+
+  #[test]
+  fn test_round_down_to_multiple() {
+    assert_eq!(round_down_to_multiple(16_usize, 8), 16);
+    assert_eq!(round_down_to_multiple(17_usize, 8), 16);
+    assert_eq!(round_down_to_multiple(7_usize, 8), 0);
+    assert_eq!(round_down_to_multiple(-1, 8), -8);
+    assert_eq!(round_down_to_multiple(-17, 8), -24);
+    assert_eq!(round_down_to_multiple(-17, -8), -24);
+    assert_eq!(round_down_to_multiple(17, -8), 16);
+    assert_eq!(round_down_to_multiple(i8::MIN, 2), i8::MIN);
+  }
+
+  #[test]
+  #[should_panic(expected = "Rounding -128 down to a multiple of 3 overflows")]
+  fn test_round_down_to_multiple_overflow() { let _ = round_down_to_multiple(i8::MIN, 3); }
+
+  #[test]
+  fn test_round_up_to_multiple() {
+    assert_eq!(round_up_to_multiple(16_usize, 8), 16);
+    assert_eq!(round_up_to_multiple(17_usize, 8), 24);
+    assert_eq!(round_up_to_multiple(0_usize, 8), 0);
+    assert_eq!(round_up_to_multiple(-1, 8), 0);
+    assert_eq!(round_up_to_multiple(-17, 8), -16);
+    assert_eq!(round_up_to_multiple(17, -8), 24);
+    assert_eq!(round_up_to_multiple(crate::TEXT_WIDTH, 4), 112);
+    assert_eq!(round_up_to_multiple(u8::MAX, 1), u8::MAX);
+  }
+
+  #[test]
+  #[should_panic(expected = "Rounding 255 up to a multiple of 2 overflows")]
+  fn test_round_up_to_multiple_overflow() { let _ = round_up_to_multiple(u8::MAX, 2); }
+
+  #[test]
+  #[should_panic(expected = "Cannot round 1 to a multiple of 0")]
+  fn test_round_up_to_multiple_zero() { let _ = round_up_to_multiple(1, 0); }
+
+  #[test]
+  fn test_std_dev() {
+    assert_eq!(std_dev(&TIMINGS, VarianceKind::Population), Some(30.0_f64.sqrt()));
+    assert_eq!(std_dev(&TIMINGS, VarianceKind::Sample), Some(36.0_f64.sqrt()));
+    assert_eq!(std_dev(&[], VarianceKind::Population), None);
+  }
+
+  #[test]
+  fn test_variance() {
+    assert_eq!(variance(&TIMINGS, VarianceKind::Population), Some(30.0));
+    assert_eq!(variance(&TIMINGS, VarianceKind::Sample), Some(36.0));
+
+    assert_eq!(variance(&[-1.5], VarianceKind::Population), Some(0.0));
+    assert_eq!(variance(&[-1.5], VarianceKind::Sample), None);
+    assert_eq!(variance(&[], VarianceKind::Population), None);
+    assert_eq!(variance(&[], VarianceKind::Sample), None);
+    assert!(variance(&[1.0, f64::NAN], VarianceKind::Sample).is_some_and(f64::is_nan));
+  }
+}
 
 // EOF
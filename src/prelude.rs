@@ -1,7 +1,36 @@
 // prelude.rs
 
 //! A prelude module for commonly used items.
+//!
+//! `use meadows::prelude::*;` imports the following items:
+//!
+//! | Item                   | Kind   | Defined In
+//! | :--------------------- | :----- | :---------
+//! | [`ExecType`]           | Enum   | [`crate::process`]
+//! | [`process_error!`]     | Macro  | [`crate::macros`]
+//! | [`process_note!`]      | Macro  | [`crate::macros`]
+//! | [`process_warn!`]      | Macro  | [`crate::macros`]
+//! | [`StrExt`]             | Trait  | [`crate::str`]
+//! | [`Uvec`]               | Struct | [`crate::collections`]
+//! | [`VecExt`]             | Trait  | [`crate::vec`]
+//!
+//! None of these names collide with items of the standard library's prelude.
+//!
+//! # Examples
+//!
+//! ```
+//! use meadows::prelude::*;
+//!
+//! let mut stdout = meadows::io::stdout().lock();
+//! process_note!(stdout, "{}", "hello".wrap(80))?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
 
+pub use crate::collections::Uvec;
+pub use crate::process::ExecType;
+pub use crate::process_error;
+pub use crate::process_note;
+pub use crate::process_warn;
 pub use crate::str::StrExt;
 pub use crate::vec::VecExt;
 
@@ -2,6 +2,32 @@
 
 //! Process-related utilities.
 
+use std::ffi::OsString;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+use std::process::Command;
+use std::process::Output;
+use std::process::Stdio;
+use std::str::FromStr;
+#[cfg(feature = "ctrlc")]
+use std::sync::Arc;
+use std::sync::OnceLock;
+#[cfg(feature = "ctrlc")]
+use std::sync::atomic::AtomicBool;
+#[cfg(feature = "ctrlc")]
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use regex::Regex;
+use thiserror::Error as ThisError;
+use tracing::info;
+
 // `ExecType` -----------------------------------------------------------------------------------------------
 
 /// An enum for the type of the Rust executable.
@@ -27,4 +53,1112 @@ impl ExecType {
   pub fn is_test(&self) -> bool { !matches!(self, Self::Binary | Self::Example) }
 }
 
+impl Display for ExecType {
+  /// Formats the executable type as a lowercase kebab-case name.
+  ///
+  /// | Variant     | Name
+  /// | :---------- | :---
+  /// | `Binary`    | `binary`
+  /// | `Example`   | `example`
+  /// | `DocTest`   | `doc-test`
+  /// | `UnitTest`  | `unit-test`
+  /// | `IntegTest` | `integration-test`
+  /// | `BenchTest` | `benchmark-test`
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      Self::Binary => "binary",
+      Self::Example => "example",
+      Self::DocTest => "doc-test",
+      Self::UnitTest => "unit-test",
+      Self::IntegTest => "integration-test",
+      Self::BenchTest => "benchmark-test",
+    })
+  }
+}
+
+impl FromStr for ExecType {
+  type Err = ParseExecTypeError;
+
+  /// Parses an executable type from the names produced by [`Display`] or from the variant names, e.g.
+  /// `unit-test` or `UnitTest`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Err`] with a [`ParseExecTypeError`] if `s` is not a known name.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::process::ExecType;
+  ///
+  /// assert_eq!("doc-test".parse::<ExecType>()?, ExecType::DocTest);
+  /// assert_eq!("IntegTest".parse::<ExecType>()?, ExecType::IntegTest);
+  /// assert!("test".parse::<ExecType>().is_err());
+  /// # Ok::<(), meadows::process::ParseExecTypeError>(())
+  /// ```
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "binary" | "Binary" => Ok(Self::Binary),
+      "example" | "Example" => Ok(Self::Example),
+      "doc-test" | "DocTest" => Ok(Self::DocTest),
+      "unit-test" | "UnitTest" => Ok(Self::UnitTest),
+      "integration-test" | "IntegTest" => Ok(Self::IntegTest),
+      "benchmark-test" | "BenchTest" => Ok(Self::BenchTest),
+      _ => Err(ParseExecTypeError(s.to_owned())),
+    }
+  }
+}
+
+// `ParseExecTypeError` -------------------------------------------------------------------------------------
+
+/// Error type for parsing an [`ExecType`], holding the string that could not be parsed.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+#[error("Unknown executable type `{0}`")]
+pub struct ParseExecTypeError(pub String);
+
+// `Priority` -----------------------------------------------------------------------------------------------
+
+/// A scheduling priority, see [`set_priority`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Priority {
+  /// The process runs only when the system is idle.
+  Idle,
+  /// The process runs with lower priority than normal processes, e.g. for background work.
+  Low,
+  /// The default priority.
+  #[default]
+  Normal,
+  /// The process runs with higher priority than normal processes.
+  High,
+}
+
+// `Stopwatch` ----------------------------------------------------------------------------------------------
+
+/// A stopwatch that measures the time spent in consecutive phases, e.g. the phases of a program's startup.
+///
+/// Each call to [`lap`] records the time elapsed since the previous lap, or since [`start`] for the first
+/// lap, under a label. This complements the start message logged by `tracing::config` with coarse timing
+/// information.
+///
+/// Time is measured using [`Instant`], i.e. a monotonic clock. Measurements are therefore unaffected by
+/// changes to the system time, but they are only meaningful within the same process.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::process::Stopwatch;
+///
+/// let mut stopwatch = Stopwatch::start();
+/// // Parse arguments ...
+/// stopwatch.lap("Argument parsing");
+/// // Load configuration ...
+/// stopwatch.lap("Configuration loading");
+///
+/// for (label, duration) in stopwatch.report() {
+///   println!("{label}: {duration:?}");
+/// }
+/// ```
+///
+/// [`lap`]: Stopwatch::lap
+/// [`start`]: Stopwatch::start
+#[derive(Clone, Debug)]
+pub struct Stopwatch {
+  laps: Vec<(String, Duration)>,
+  last: Instant,
+}
+
+impl Stopwatch {
+  /// Records a lap labeled `label` and returns the time elapsed since the previous lap, or since the
+  /// stopwatch was started for the first lap.
+  pub fn lap(&mut self, label: &str) -> Duration {
+    let now = Instant::now();
+    let duration = now.duration_since(self.last);
+    self.last = now;
+    self.laps.push((label.to_owned(), duration));
+    duration
+  }
+
+  /// Logs each lap recorded so far as an `INFO` event.
+  pub fn log_report(&self) {
+    for (label, duration) in &self.laps {
+      info!("{label}: {duration:?}");
+    }
+  }
+
+  /// Returns the labels and durations of the laps recorded so far, in the order they were recorded.
+  #[must_use]
+  pub fn report(&self) -> Vec<(String, Duration)> { self.laps.clone() }
+
+  /// Creates and starts a new stopwatch.
+  #[must_use]
+  pub fn start() -> Self { Self { laps: Vec::new(), last: Instant::now() } }
+}
+
+// Functions ------------------------------------------------------------------------------------------------
+
+/// Guesses the [`ExecType`] of the running executable from its invocation path.
+///
+/// The function applies the following heuristics to the path returned by
+/// [`inv_path`](crate::env::inv_path), in order. See there for typical paths. A hash suffix is a `-`
+/// followed by 16 hexadecimal digits, as appended by Cargo:
+///
+/// | Heuristic                                                     | Result
+/// | :------------------------------------------------------------ | :-----
+/// | The file stem is `rust_out`                                   | [`DocTest`]
+/// | The parent directory is named `examples`                      | [`Example`]
+/// | The file stem has a hash suffix, and an argument is `--bench` | [`BenchTest`]
+/// | The file stem without the hash suffix is the package name     | [`UnitTest`]
+/// | The file stem has a hash suffix                               | [`IntegTest`]
+/// | Otherwise                                                     | [`Binary`]
+///
+/// The package name is taken from the environment variable `CARGO_PKG_NAME`, which Cargo sets when running
+/// an executable, with `-` replaced by `_`.
+///
+/// The result is a best guess. In particular, unit tests of a binary target, whose name differs from the
+/// package name, are reported as [`IntegTest`], and so are unit tests run without Cargo. A binary that is
+/// copied into an `examples` directory, or whose name happens to end with a hash, is misdetected as well.
+/// If the type is known, pass it explicitly instead.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::process;
+/// use meadows::process::ExecType;
+///
+/// assert_eq!(process::detect_exec_type(), ExecType::DocTest);
+/// ```
+///
+/// [`Binary`]: ExecType::Binary
+/// [`Example`]: ExecType::Example
+/// [`DocTest`]: ExecType::DocTest
+/// [`UnitTest`]: ExecType::UnitTest
+/// [`IntegTest`]: ExecType::IntegTest
+/// [`BenchTest`]: ExecType::BenchTest
+#[must_use]
+pub fn detect_exec_type() -> ExecType {
+  let pkg_name = crate::env::get("CARGO_PKG_NAME").map(|val| val.to_string_lossy().replace('-', "_"));
+  let is_bench = std::env::args_os().skip(1).any(|arg| arg == "--bench");
+  detect_exec_type_impl(crate::env::inv_path(), pkg_name.as_deref(), is_bench)
+}
+
+fn detect_exec_type_impl(path: &Path, pkg_name: Option<&str>, is_bench: bool) -> ExecType {
+  let stem = path.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+  if stem == "rust_out" {
+    return ExecType::DocTest;
+  }
+  if path.parent().and_then(Path::file_name).is_some_and(|name| name == "examples") {
+    return ExecType::Example;
+  }
+
+  let re = Regex::new("-[0-9a-f]{16}$").unwrap();
+  let Some(m) = re.find(&stem) else {
+    return ExecType::Binary;
+  };
+  if is_bench {
+    ExecType::BenchTest
+  } else if pkg_name == Some(&stem[..m.start()]) {
+    ExecType::UnitTest
+  } else {
+    ExecType::IntegTest
+  }
+}
+
+/// Returns the value of the environment variable `key` if it is valid Unicode and not empty.
+#[cfg(any(unix, windows))]
+fn env_username(key: &str) -> Option<String> {
+  crate::env::get(key).and_then(|val| val.into_string().ok()).filter(|val| !val.is_empty())
+}
+
+/// Installs a handler that requests a graceful shutdown when the process is interrupted, and returns the
+/// flag set by the handler.
+///
+/// The handler is registered with the `ctrlc` crate. On Unix, it is called on `SIGINT`, e.g. when the user
+/// presses Ctrl-C, and on `SIGTERM` and `SIGHUP`. On Windows, it is called on Ctrl-C and Ctrl-Break, and
+/// when the console is closed. The first time it is called, the handler sets the returned flag to `true`. A
+/// long-running program polls the flag and, once it is set, stops its work and returns from `main`, so
+/// destructors run and pending output, e.g. log messages, is flushed.
+///
+/// If the process is interrupted a second time, the handler assumes the program does not respond and exits
+/// the process immediately with exit code 130, without running destructors.
+///
+/// A process can install only one handler. Calling this function a second time is an error, as is calling
+/// it after another handler has been registered with `ctrlc`.
+///
+/// # Errors
+///
+/// Returns [`Err`] with
+///
+/// - [`io::ErrorKind::AlreadyExists`] if a handler has already been installed
+/// - Any other [`io::Error`] if the handler cannot be registered with the operating system
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::atomic::Ordering;
+///
+/// use meadows::process;
+///
+/// let shutdown = process::install_shutdown_handler()?;
+/// while !shutdown.load(Ordering::Relaxed) {
+///   // Do some work
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "ctrlc")]
+pub fn install_shutdown_handler() -> io::Result<Arc<AtomicBool>> {
+  let ret = Arc::new(AtomicBool::new(false));
+  let is_requested = Arc::clone(&ret);
+  let result = ctrlc::set_handler(move || {
+    if is_requested.swap(true, Ordering::SeqCst) {
+      std::process::exit(130);
+    }
+  });
+  match result {
+    Ok(()) => Ok(ret),
+    Err(ctrlc::Error::MultipleHandlers) => {
+      Err(io::Error::new(io::ErrorKind::AlreadyExists, "A shutdown handler is already installed"))
+    }
+    Err(ctrlc::Error::System(err)) => Err(err),
+    Err(err) => Err(io::Error::other(err)),
+  }
+}
+
+/// Returns the number of file descriptors or handles the process has open.
+///
+/// This is useful for tracking down descriptor leaks in long-running processes. The count includes the
+/// standard streams `stdin`, `stdout`, and `stderr`, if open. It is determined as follows:
+///
+/// | Platform | Source
+/// | :------- | :-----
+/// | Linux    | The entries of the directory `/proc/self/fd`, not counting the descriptor used to read the directory itself
+/// | macOS    | The descriptors listed by `proc_pidinfo` with `PROC_PIDLISTFDS`
+/// | Windows  | The handle count reported by `GetProcessHandleCount`. This includes all kernel-object handles, not just file handles
+///
+/// Returns [`None`] if the count cannot be determined, e.g. on other platforms.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::process;
+///
+/// if let Some(count) = process::open_fd_count() {
+///   println!("{count} file descriptors open");
+/// }
+/// ```
+#[must_use]
+pub fn open_fd_count() -> Option<usize> { open_fd_count_impl() }
+
+#[cfg(target_os = "linux")]
+fn open_fd_count_impl() -> Option<usize> {
+  let count = std::fs::read_dir("/proc/self/fd").ok()?.count();
+  // `read_dir` holds a descriptor of its own while iterating
+  count.checked_sub(1)
+}
+
+#[cfg(target_os = "macos")]
+fn open_fd_count_impl() -> Option<usize> {
+  use std::mem;
+  use std::ptr;
+
+  let entry_size = mem::size_of::<libc::proc_fdinfo>();
+  let pid = unsafe { libc::getpid() };
+
+  // SAFETY: With a null buffer, `proc_pidinfo` only returns the buffer size required
+  let size = unsafe { libc::proc_pidinfo(pid, libc::PROC_PIDLISTFDS, 0, ptr::null_mut(), 0) };
+  let len = usize::try_from(size).ok()? / entry_size;
+
+  // Descriptors may be opened between both calls, so the buffer is somewhat larger than required
+  let mut buf: Vec<libc::proc_fdinfo> = Vec::with_capacity(len + 16);
+  let buf_size = i32::try_from(buf.capacity() * entry_size).ok()?;
+  // SAFETY: `buf` is valid for `buf_size` bytes
+  let size = unsafe { libc::proc_pidinfo(pid, libc::PROC_PIDLISTFDS, 0, buf.as_mut_ptr().cast(), buf_size) };
+  Some(usize::try_from(size).ok()? / entry_size)
+}
+
+#[cfg(windows)]
+fn open_fd_count_impl() -> Option<usize> {
+  use windows_sys::Win32::System::Threading::GetCurrentProcess;
+  use windows_sys::Win32::System::Threading::GetProcessHandleCount;
+
+  let mut count: u32 = 0;
+  // SAFETY: `GetCurrentProcess` returns a pseudo handle that is always valid
+  if unsafe { GetProcessHandleCount(GetCurrentProcess(), &raw mut count) } == 0 {
+    return None;
+  }
+  usize::try_from(count).ok()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn open_fd_count_impl() -> Option<usize> { None }
+
+/// Returns the process ID of the parent process.
+///
+/// | Platform | Source
+/// | :------- | :-----
+/// | Unix     | `getppid`. If the parent has exited, this is the ID of the process the current process has been reparented to, usually `1`
+/// | Windows  | The process entry of the current process in a toolhelp snapshot, see `CreateToolhelp32Snapshot`
+///
+/// Returns [`None`] if the ID cannot be determined, e.g. on other platforms.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::process;
+///
+/// if let Some(parent_id) = process::parent_id() {
+///   println!("Launched by process {parent_id}");
+/// }
+/// ```
+#[must_use]
+pub fn parent_id() -> Option<u32> { parent_id_impl() }
+
+#[cfg(unix)]
+fn parent_id_impl() -> Option<u32> {
+  // SAFETY: `getppid` is always successful
+  let ppid = unsafe { libc::getppid() };
+  u32::try_from(ppid).ok()
+}
+
+#[cfg(windows)]
+fn parent_id_impl() -> Option<u32> {
+  // SAFETY: `GetCurrentProcessId` is always successful
+  let pid = unsafe { windows_sys::Win32::System::Threading::GetCurrentProcessId() };
+  process_entry(pid).map(|entry| entry.th32ParentProcessID)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn parent_id_impl() -> Option<u32> { None }
+
+/// Returns the name of the parent process, see [`parent_id`].
+///
+/// | Platform | Source
+/// | :------- | :-----
+/// | Linux    | The file `/proc/<pid>/comm`. The kernel truncates the name to 15 bytes
+/// | macOS    | `proc_name`
+/// | Windows  | The executable file name from the process entry of the parent in a toolhelp snapshot
+///
+/// Returns [`None`] if the name cannot be determined, e.g. because the parent has exited, or on other
+/// platforms.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::process;
+///
+/// if let Some(parent_name) = process::parent_name() {
+///   println!("Launched by {}", parent_name.display());
+/// }
+/// ```
+#[must_use]
+pub fn parent_name() -> Option<OsString> { parent_name_impl() }
+
+#[cfg(target_os = "linux")]
+fn parent_name_impl() -> Option<OsString> {
+  use std::os::unix::ffi::OsStringExt;
+
+  let mut name = std::fs::read(format!("/proc/{}/comm", parent_id()?)).ok()?;
+  if name.last() == Some(&b'\n') {
+    name.pop();
+  }
+  (!name.is_empty()).then(|| OsString::from_vec(name))
+}
+
+#[cfg(target_os = "macos")]
+fn parent_name_impl() -> Option<OsString> {
+  use std::os::unix::ffi::OsStringExt;
+
+  let ppid = libc::pid_t::try_from(parent_id()?).ok()?;
+  let mut buf = vec![0u8; 4 * libc::MAXCOMLEN];
+  let buf_len = u32::try_from(buf.len()).ok()?;
+  // SAFETY: `buf` is valid for writes of `buf_len` bytes
+  let len = unsafe { libc::proc_name(ppid, buf.as_mut_ptr().cast(), buf_len) };
+  let len = usize::try_from(len).ok().filter(|&len| len > 0)?;
+  buf.truncate(len);
+  Some(OsString::from_vec(buf))
+}
+
+#[cfg(windows)]
+fn parent_name_impl() -> Option<OsString> {
+  use std::os::windows::ffi::OsStringExt;
+
+  let entry = process_entry(parent_id()?)?;
+  let len = entry.szExeFile.iter().position(|&c| c == 0).unwrap_or(entry.szExeFile.len());
+  (len > 0).then(|| OsString::from_wide(&entry.szExeFile[..len]))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn parent_name_impl() -> Option<OsString> { None }
+
+/// Returns the entry of the process with ID `pid` from a toolhelp snapshot of all processes.
+#[cfg(windows)]
+fn process_entry(pid: u32) -> Option<windows_sys::Win32::System::Diagnostics::ToolHelp::PROCESSENTRY32W> {
+  use windows_sys::Win32::Foundation::CloseHandle;
+  use windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE;
+  use windows_sys::Win32::System::Diagnostics::ToolHelp::CreateToolhelp32Snapshot;
+  use windows_sys::Win32::System::Diagnostics::ToolHelp::PROCESSENTRY32W;
+  use windows_sys::Win32::System::Diagnostics::ToolHelp::Process32FirstW;
+  use windows_sys::Win32::System::Diagnostics::ToolHelp::Process32NextW;
+  use windows_sys::Win32::System::Diagnostics::ToolHelp::TH32CS_SNAPPROCESS;
+
+  // SAFETY: `CreateToolhelp32Snapshot` has no memory-safety preconditions
+  let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) };
+  if snapshot == INVALID_HANDLE_VALUE {
+    return None;
+  }
+
+  let mut entry = PROCESSENTRY32W {
+    dwSize: u32::try_from(std::mem::size_of::<PROCESSENTRY32W>()).unwrap(),
+    ..PROCESSENTRY32W::default()
+  };
+  let mut ret = None;
+  // SAFETY: `snapshot` is a valid snapshot handle, and `entry.dwSize` is set as required
+  let mut ok = unsafe { Process32FirstW(snapshot, &raw mut entry) } != 0;
+  while ok {
+    if entry.th32ProcessID == pid {
+      ret = Some(entry);
+      break;
+    }
+    // SAFETY: See above
+    ok = unsafe { Process32NextW(snapshot, &raw mut entry) } != 0;
+  }
+  // SAFETY: `snapshot` is a valid handle that is not used afterwards
+  unsafe { CloseHandle(snapshot) };
+  ret
+}
+
+/// Runs a command as a child process, collecting its output, and kills it if it does not finish in time.
+///
+/// This works like [`Command::output`], i.e. `stdout` and `stderr` of the child are captured, but the
+/// function waits at most `timeout` for the child to exit. Unlike with [`Command::output`], `stdin` is
+/// inherited unless configured otherwise, e.g. via [`Stdio::null`](std::process::Stdio::null).
+///
+/// If the child has not exited by the deadline, it is killed and waited for, and an error of kind
+/// [`io::ErrorKind::TimedOut`] is returned. The output captured so far is discarded. If the child exits
+/// right at the deadline, it is checked once more before being killed, so a child that has exited in time
+/// is never reported as timed out.
+///
+/// The child's status is polled in short intervals, so the function may return up to 10 ms after the child
+/// has exited.
+///
+/// # Errors
+///
+/// Returns [`Err`] if the child cannot be spawned or waited for, or with [`io::ErrorKind::TimedOut`] if it
+/// does not finish in time.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::process::Command;
+/// use std::time::Duration;
+///
+/// use meadows::process;
+///
+/// let output = process::run_with_timeout(&mut Command::new("git").arg("status"), Duration::from_secs(5))?;
+/// println!("{}", String::from_utf8_lossy(&output.stdout));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn run_with_timeout(cmd: &mut Command, timeout: Duration) -> io::Result<Output> {
+  const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+  let deadline = Instant::now() + timeout;
+  let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+
+  // Read both pipes concurrently, so the child never blocks on a full pipe
+  let read_pipe = |pipe: Option<Box<dyn Read + Send>>| {
+    thread::spawn(move || -> io::Result<Vec<u8>> {
+      let mut ret = Vec::new();
+      if let Some(mut pipe) = pipe {
+        pipe.read_to_end(&mut ret)?;
+      }
+      Ok(ret)
+    })
+  };
+  let stdout = read_pipe(child.stdout.take().map(|pipe| Box::new(pipe) as Box<dyn Read + Send>));
+  let stderr = read_pipe(child.stderr.take().map(|pipe| Box::new(pipe) as Box<dyn Read + Send>));
+
+  let status = loop {
+    if let Some(status) = child.try_wait()? {
+      break status;
+    }
+    let now = Instant::now();
+    if now >= deadline {
+      // Check once more, then kill. The reading threads are detached, since grandchildren may keep the
+      // pipes open
+      if let Some(status) = child.try_wait()? {
+        break status;
+      }
+      child.kill()?;
+      child.wait()?;
+      return Err(io::Error::new(io::ErrorKind::TimedOut, format!("Process timed out after {timeout:?}")));
+    }
+    thread::sleep(POLL_INTERVAL.min(deadline - now));
+  };
+
+  let join = |handle: thread::JoinHandle<io::Result<Vec<u8>>>| {
+    handle.join().unwrap_or_else(|_| Err(io::Error::other("Cannot read output of process")))
+  };
+  Ok(Output { status, stdout: join(stdout)?, stderr: join(stderr)? })
+}
+
+/// Sets the scheduling priority of the current process.
+///
+/// The priority maps to the following platform-specific values:
+///
+/// | [`Priority`] | Unix Nice Value | Windows Priority Class
+/// | :----------- | :-------------- | :---------------------
+/// | `Idle`       | 19              | `IDLE_PRIORITY_CLASS`
+/// | `Low`        | 10              | `BELOW_NORMAL_PRIORITY_CLASS`
+/// | `Normal`     | 0               | `NORMAL_PRIORITY_CLASS`
+/// | `High`       | -10             | `HIGH_PRIORITY_CLASS`
+///
+/// On Unix, the nice value is set via `setpriority`. Note that on Linux, this only affects the calling
+/// thread and threads created by it afterwards, so the function should be called early, before any threads
+/// are spawned. On Windows, the priority class is set via `SetPriorityClass`.
+///
+/// # Errors
+///
+/// Returns [`Err`] if the priority cannot be set. In particular, raising the priority usually requires
+/// privileges. On Unix, this includes decreasing the nice value, e.g. going back from `Low` to `Normal`.
+/// Without privileges, an error of kind [`io::ErrorKind::PermissionDenied`] is returned. On other
+/// platforms, an error of kind [`io::ErrorKind::Unsupported`] is returned.
+///
+/// # Examples
+///
+/// ```no_run
+/// use meadows::process;
+/// use meadows::process::Priority;
+///
+/// process::set_priority(Priority::Low)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn set_priority(priority: Priority) -> io::Result<()> { set_priority_impl(priority) }
+
+#[cfg(unix)]
+fn set_priority_impl(priority: Priority) -> io::Result<()> {
+  let nice = match priority {
+    Priority::Idle => 19,
+    Priority::Low => 10,
+    Priority::Normal => 0,
+    Priority::High => -10,
+  };
+  // SAFETY: `setpriority` has no memory-safety preconditions
+  if unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, nice) } != 0 {
+    return Err(io::Error::last_os_error());
+  }
+  Ok(())
+}
+
+#[cfg(windows)]
+fn set_priority_impl(priority: Priority) -> io::Result<()> {
+  use windows_sys::Win32::System::Threading::BELOW_NORMAL_PRIORITY_CLASS;
+  use windows_sys::Win32::System::Threading::GetCurrentProcess;
+  use windows_sys::Win32::System::Threading::HIGH_PRIORITY_CLASS;
+  use windows_sys::Win32::System::Threading::IDLE_PRIORITY_CLASS;
+  use windows_sys::Win32::System::Threading::NORMAL_PRIORITY_CLASS;
+  use windows_sys::Win32::System::Threading::SetPriorityClass;
+
+  let priority_class = match priority {
+    Priority::Idle => IDLE_PRIORITY_CLASS,
+    Priority::Low => BELOW_NORMAL_PRIORITY_CLASS,
+    Priority::Normal => NORMAL_PRIORITY_CLASS,
+    Priority::High => HIGH_PRIORITY_CLASS,
+  };
+  // SAFETY: `GetCurrentProcess` returns a pseudo handle that is always valid
+  if unsafe { SetPriorityClass(GetCurrentProcess(), priority_class) } == 0 {
+    return Err(io::Error::last_os_error());
+  }
+  Ok(())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn set_priority_impl(_priority: Priority) -> io::Result<()> {
+  Err(io::Error::new(io::ErrorKind::Unsupported, "Setting the priority is not supported"))
+}
+
+/// Returns the [`Instant`] the process is considered to have started at.
+///
+/// The instant is captured lazily on the first call, so it is only as accurate as that call is early.
+/// Ideally, call this function at the very beginning of `main`. `crate::tracing::config::try_init` and
+/// `crate::tracing::config::init` call it as well.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::process;
+///
+/// let start = process::start_instant();
+/// assert_eq!(process::start_instant(), start);
+/// ```
+#[must_use]
+pub fn start_instant() -> Instant {
+  static VAL: OnceLock<Instant> = OnceLock::new();
+  *VAL.get_or_init(Instant::now)
+}
+
+/// Checks if `stdout` and `stderr` write to the same destination, e.g. the same terminal or the same file.
+///
+/// The comparison depends on the platform:
+///
+/// | Platform | Comparison
+/// | :------- | :---------
+/// | Unix     | Both file descriptors refer to the same device and inode, as reported by `fstat`
+/// | Windows  | Both handles are consoles, or both handles refer to the same volume serial number and file index, as reported by `GetFileInformationByHandle`
+///
+/// Returns `false` if the destinations cannot be determined, e.g. on other platforms.
+#[must_use]
+pub fn stdout_is_stderr() -> bool { stdout_is_stderr_impl() }
+
+#[cfg(unix)]
+fn stdout_is_stderr_impl() -> bool {
+  use std::fs::File;
+  use std::os::fd::AsFd;
+  use std::os::fd::BorrowedFd;
+  use std::os::unix::fs::MetadataExt;
+
+  let file_id = |fd: BorrowedFd| -> Option<(u64, u64)> {
+    let metadata = File::from(fd.try_clone_to_owned().ok()?).metadata().ok()?;
+    Some((metadata.dev(), metadata.ino()))
+  };
+
+  match (file_id(io::stdout().as_fd()), file_id(io::stderr().as_fd())) {
+    (Some(stdout_id), Some(stderr_id)) => stdout_id == stderr_id,
+    _ => false,
+  }
+}
+
+#[cfg(windows)]
+fn stdout_is_stderr_impl() -> bool {
+  use std::io::IsTerminal;
+  use std::mem;
+  use std::os::windows::io::AsRawHandle;
+  use std::os::windows::io::RawHandle;
+
+  use windows_sys::Win32::Storage::FileSystem::BY_HANDLE_FILE_INFORMATION;
+  use windows_sys::Win32::Storage::FileSystem::GetFileInformationByHandle;
+
+  let stdout = io::stdout();
+  let stderr = io::stderr();
+
+  // Console handles cannot be queried for file information
+  if stdout.is_terminal() && stderr.is_terminal() {
+    return true;
+  }
+
+  let file_id = |handle: RawHandle| -> Option<(u32, u32, u32)> {
+    // SAFETY: `BY_HANDLE_FILE_INFORMATION` is a plain C struct, and `handle` is a valid standard handle
+    let mut info: BY_HANDLE_FILE_INFORMATION = unsafe { mem::zeroed() };
+    if unsafe { GetFileInformationByHandle(handle, &raw mut info) } == 0 {
+      return None;
+    }
+    Some((info.dwVolumeSerialNumber, info.nFileIndexHigh, info.nFileIndexLow))
+  };
+
+  match (file_id(stdout.as_raw_handle()), file_id(stderr.as_raw_handle())) {
+    (Some(stdout_id), Some(stderr_id)) => stdout_id == stderr_id,
+    _ => false,
+  }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn stdout_is_stderr_impl() -> bool { false }
+
+/// Checks if a debugger is attached to the process.
+///
+/// | Platform | Check
+/// | :------- | :----
+/// | Linux    | The `TracerPid` field in `/proc/self/status` is not 0
+/// | macOS    | The `PROC_FLAG_TRACED` flag as reported by `proc_pidinfo` with `PROC_PIDTBSDINFO`, which mirrors the `P_TRACED` flag reported by `sysctl`
+/// | Windows  | `IsDebuggerPresent`
+///
+/// This is a best-effort check. It samples the state at call time, so the result may change during
+/// execution, e.g. if a debugger is attached later. On Linux, any tracer counts, e.g. `strace`. Returns
+/// `false` if the state cannot be determined, e.g. on other platforms.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::process;
+///
+/// if !process::under_debugger() {
+///   // Timing-sensitive checks
+/// }
+/// ```
+#[must_use]
+pub fn under_debugger() -> bool { under_debugger_impl() }
+
+#[cfg(target_os = "linux")]
+fn under_debugger_impl() -> bool {
+  let Ok(status) = std::fs::read_to_string("/proc/self/status") else {
+    return false;
+  };
+  status
+    .lines()
+    .find_map(|line| line.strip_prefix("TracerPid:"))
+    .is_some_and(|pid| pid.trim() != "0")
+}
+
+#[cfg(target_os = "macos")]
+fn under_debugger_impl() -> bool {
+  use std::mem;
+
+  // From `<sys/proc_info.h>`
+  const PROC_FLAG_TRACED: u32 = 0x2;
+
+  let Ok(size) = i32::try_from(mem::size_of::<libc::proc_bsdinfo>()) else {
+    return false;
+  };
+  // SAFETY: `proc_bsdinfo` is a plain C struct for which all zeros is a valid value
+  let mut info: libc::proc_bsdinfo = unsafe { mem::zeroed() };
+  // SAFETY: `getpid` is always successful, and `info` is valid for `size` bytes
+  let ret = unsafe {
+    libc::proc_pidinfo(libc::getpid(), libc::PROC_PIDTBSDINFO, 0, (&raw mut info).cast(), size)
+  };
+  ret == size && info.pbi_flags & PROC_FLAG_TRACED != 0
+}
+
+#[cfg(windows)]
+fn under_debugger_impl() -> bool {
+  // SAFETY: `IsDebuggerPresent` has no preconditions
+  unsafe { windows_sys::Win32::System::Diagnostics::Debug::IsDebuggerPresent() != 0 }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn under_debugger_impl() -> bool { false }
+
+/// Returns the time elapsed since [`start_instant`].
+///
+/// The result is only as accurate as the first call to [`start_instant`] is early.
+#[must_use]
+pub fn uptime() -> Duration { start_instant().elapsed() }
+
+/// Returns the login name of the user running the process.
+///
+/// The name is determined as follows, and the first non-empty value is returned:
+///
+/// | Platform | Fallback Chain
+/// | :------- | :-------------
+/// | Unix     | Environment variable `USER`, environment variable `LOGNAME`, the name of the effective user as reported by `getpwuid_r(geteuid())`
+/// | Windows  | Environment variable `USERNAME`, the name reported by `GetUserNameW`
+///
+/// Returns [`None`] if no value is found, a value is not valid Unicode, or on other platforms.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::process;
+///
+/// if let Some(username) = process::username() {
+///   println!("Running as {username}");
+/// }
+/// ```
+#[must_use]
+pub fn username() -> Option<String> { username_impl() }
+
+#[cfg(unix)]
+fn username_impl() -> Option<String> {
+  use std::ffi::CStr;
+  use std::mem;
+  use std::ptr;
+
+  let user_from_passwd = || -> Option<String> {
+    let mut buf = vec![0; 1024];
+    loop {
+      // SAFETY: `passwd` is a plain C struct, and all pointers are valid for the duration of the call
+      let mut passwd: libc::passwd = unsafe { mem::zeroed() };
+      let mut result = ptr::null_mut();
+      let err = unsafe {
+        libc::getpwuid_r(libc::geteuid(), &raw mut passwd, buf.as_mut_ptr(), buf.len(), &raw mut result)
+      };
+      if err == libc::ERANGE && buf.len() < 1 << 20 {
+        buf.resize(buf.len() * 2, 0);
+        continue;
+      }
+      if err != 0 || result.is_null() || passwd.pw_name.is_null() {
+        return None;
+      }
+      // SAFETY: `pw_name` points to a NUL-terminated string within `buf`
+      let name = unsafe { CStr::from_ptr(passwd.pw_name) };
+      return name.to_str().ok().map(String::from);
+    }
+  };
+
+  env_username("USER")
+    .or_else(|| env_username("LOGNAME"))
+    .or_else(|| user_from_passwd().filter(|val| !val.is_empty()))
+}
+
+#[cfg(windows)]
+fn username_impl() -> Option<String> {
+  use windows_sys::Win32::System::WindowsProgramming::GetUserNameW;
+
+  let user_from_api = || -> Option<String> {
+    // `UNLEN` + 1
+    let mut len: u32 = 257;
+    let mut buf = vec![0u16; len as usize];
+    // SAFETY: `buf` is valid for `len` UTF-16 code units
+    if unsafe { GetUserNameW(buf.as_mut_ptr(), &raw mut len) } == 0 || len == 0 {
+      return None;
+    }
+    // `len` includes the terminating NUL
+    String::from_utf16(&buf[..len as usize - 1]).ok()
+  };
+
+  env_username("USERNAME").or_else(|| user_from_api().filter(|val| !val.is_empty()))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn username_impl() -> Option<String> { None }
+
+// Tests ====================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+  use std::fs::File;
+
+  use super::*;
+
+  /// Runs the test named `test_name` from this test executable in a child process, redirecting its `stdout`
+  /// and `stderr`.
+  fn run_child(test_name: &str, stdout: File, stderr: File) -> io::Result<()> {
+    let status = Command::new(std::env::current_exe()?)
+      .args(["--exact", test_name, "--nocapture", "--test-threads", "1"])
+      .env("MEADOWS_TEST_CHILD", "1")
+      .stdin(Stdio::null())
+      .stdout(stdout)
+      .stderr(stderr)
+      .status()?;
+    assert!(status.success());
+    Ok(())
+  }
+
+  // `ExecType` ---------------------------------------------------------------------------------------------
+
+  #[test]
+  fn test_exec_type_display_from_str() {
+    use ExecType::*;
+
+    for exec_type in [Binary, Example, DocTest, UnitTest, IntegTest, BenchTest] {
+      assert_eq!(exec_type.to_string().parse::<ExecType>(), Ok(exec_type));
+      assert_eq!(format!("{exec_type:?}").parse::<ExecType>(), Ok(exec_type));
+    }
+    assert_eq!(IntegTest.to_string(), "integration-test");
+    assert_eq!("".parse::<ExecType>(), Err(ParseExecTypeError(String::new())));
+    assert_eq!("Unit-Test".parse::<ExecType>(), Err(ParseExecTypeError(String::from("Unit-Test"))));
+  }
+
+  // `Stopwatch` --------------------------------------------------------------------------------------------
+
+  #[test]
+  fn test_stopwatch() {
+    let mut stopwatch = Stopwatch::start();
+    thread::sleep(Duration::from_millis(10));
+    let duration_1 = stopwatch.lap("one");
+    thread::sleep(Duration::from_millis(20));
+    let duration_2 = stopwatch.lap("two");
+
+    assert!(duration_1 >= Duration::from_millis(10));
+    assert!(duration_2 >= Duration::from_millis(20));
+
+    let report = stopwatch.report();
+    assert_eq!(report, vec![(String::from("one"), duration_1), (String::from("two"), duration_2)]);
+    stopwatch.log_report();
+  }
+
+  // Functions ----------------------------------------------------------------------------------------------
+
+  #[test]
+  fn test_detect_exec_type() {
+    // This is a unit test, run by Cargo or not
+    assert!(matches!(detect_exec_type(), ExecType::UnitTest | ExecType::IntegTest));
+  }
+
+  #[test]
+  fn test_detect_exec_type_impl() {
+    let detect = |path: &str, is_bench| detect_exec_type_impl(Path::new(path), Some("my_app"), is_bench);
+
+    assert_eq!(detect("target/debug/my-app", false), ExecType::Binary);
+    assert_eq!(detect("/usr/local/bin/my-app", false), ExecType::Binary);
+    assert_eq!(detect("target/debug/examples/demo", false), ExecType::Example);
+    assert_eq!(detect("/tmp/rustdoctestDWexge/rust_out", false), ExecType::DocTest);
+    assert_eq!(detect("target/debug/deps/src_lib_rs_12_0/rust_out", false), ExecType::DocTest);
+    assert_eq!(detect("target/debug/deps/my_app-c905bc0db64270b7", false), ExecType::UnitTest);
+    assert_eq!(detect("target/debug/deps/test_std-df01c96339a9b446", false), ExecType::IntegTest);
+    assert_eq!(detect("target/release/deps/bench_std-f98027946d1328b1", true), ExecType::BenchTest);
+    assert_eq!(detect("target/debug/deps/my_app-c905bc0db64270b7.exe", false), ExecType::UnitTest);
+
+    // Not a hash
+    assert_eq!(detect("target/debug/my_app-0123", false), ExecType::Binary);
+  }
+
+  #[cfg(all(unix, feature = "ctrlc"))]
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_install_shutdown_handler() -> io::Result<()> {
+    let test_name = "process::tests::test_install_shutdown_handler";
+
+    // In the child process, report the result on `stderr`
+    if crate::env::get("MEADOWS_TEST_CHILD").is_some() {
+      let shutdown = install_shutdown_handler()?;
+      assert!(!shutdown.load(Ordering::SeqCst));
+      let err = install_shutdown_handler().unwrap_err();
+      assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+      // SAFETY: The handler installed above only sets the flag on the first signal
+      unsafe { libc::raise(libc::SIGTERM) };
+      // The handler runs on a separate thread, so wait for it
+      let deadline = Instant::now() + Duration::from_secs(5);
+      while !shutdown.load(Ordering::SeqCst) && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+      }
+      eprintln!("shutdown: {}", shutdown.load(Ordering::SeqCst));
+      return Ok(());
+    }
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("stderr.txt");
+    run_child(test_name, File::create(dir.path().join("stdout.txt"))?, File::create(&path)?)?;
+    assert!(fs::read_to_string(&path)?.contains("shutdown: true"));
+
+    Ok(())
+  }
+
+  #[cfg(any(target_os = "linux", target_os = "macos", windows))]
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_open_fd_count() -> io::Result<()> {
+    // At least `stdin`, `stdout`, and `stderr`. Other tests may open or close files concurrently, so the
+    // exact count is not predictable
+    let count = open_fd_count().unwrap();
+    assert!(count >= 3, "{count}");
+
+    let dir = tempfile::tempdir()?;
+    let _file = File::create(dir.path().join("file.txt"))?;
+    assert!(open_fd_count().unwrap() >= 4);
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_parent_id() {
+    let parent_id = parent_id();
+    if cfg!(any(unix, windows)) {
+      assert!(parent_id.is_some());
+      assert_ne!(parent_id, Some(std::process::id()));
+    } else {
+      assert_eq!(parent_id, None);
+    }
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_parent_name() {
+    let parent_name = parent_name();
+    if cfg!(any(target_os = "linux", target_os = "macos", windows)) {
+      assert!(!parent_name.unwrap().is_empty());
+    } else {
+      assert_eq!(parent_name, None);
+    }
+  }
+
+  #[cfg(unix)]
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_run_with_timeout() -> io::Result<()> {
+    let sh = |script: &str| {
+      let mut cmd = Command::new("sh");
+      cmd.args(["-c", script]).stdin(Stdio::null());
+      cmd
+    };
+
+    let output = run_with_timeout(&mut sh("echo out; echo err >&2; exit 3"), Duration::from_secs(10))?;
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(output.stdout, b"out\n");
+    assert_eq!(output.stderr, b"err\n");
+
+    let start = Instant::now();
+    let err = run_with_timeout(&mut sh("sleep 10"), Duration::from_millis(100)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    // A large output does not block the child
+    let output = run_with_timeout(&mut sh("head -c 1000000 /dev/zero"), Duration::from_secs(10))?;
+    assert_eq!(output.stdout.len(), 1_000_000);
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_set_priority() -> io::Result<()> {
+    // On Linux, this only affects the test thread. Lowering the priority never requires privileges
+    set_priority(Priority::Low)?;
+
+    // Raising it again may require privileges
+    if let Err(err) = set_priority(Priority::Normal) {
+      assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+    }
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_start_instant() {
+    let start = start_instant();
+    assert_eq!(start_instant(), start);
+    assert!(start <= Instant::now());
+  }
+
+  #[test]
+  fn test_stdout_is_stderr() -> io::Result<()> {
+    let test_name = "process::tests::test_stdout_is_stderr";
+
+    // In the child process, report the result on `stderr`
+    if crate::env::get("MEADOWS_TEST_CHILD").is_some() {
+      eprintln!("stdout_is_stderr: {}", stdout_is_stderr());
+      return Ok(());
+    }
+
+    let dir = tempfile::tempdir()?;
+
+    // Same file
+    let path = dir.path().join("out.txt");
+    let file = File::create(&path)?;
+    run_child(test_name, file.try_clone()?, file)?;
+    assert!(fs::read_to_string(&path)?.contains("stdout_is_stderr: true"));
+
+    // Different files
+    let stdout_path = dir.path().join("stdout.txt");
+    let stderr_path = dir.path().join("stderr.txt");
+    run_child(test_name, File::create(&stdout_path)?, File::create(&stderr_path)?)?;
+    assert!(fs::read_to_string(&stderr_path)?.contains("stdout_is_stderr: false"));
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_under_debugger() {
+    // Usually, no debugger is attached, but this cannot be asserted
+    let _ = under_debugger();
+  }
+
+  #[test]
+  fn test_uptime() {
+    let uptime_1 = uptime();
+    thread::sleep(Duration::from_millis(10));
+    let uptime_2 = uptime();
+    assert!(uptime_2 >= uptime_1 + Duration::from_millis(10));
+  }
+
+  #[test]
+  fn test_username() {
+    // In a normal environment, there is a user name. If there is none, `None` is returned gracefully
+    if let Some(username) = username() {
+      assert!(!username.is_empty());
+    }
+  }
+}
+
 // EOF
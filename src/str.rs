@@ -2,6 +2,13 @@
 
 //! String-related utilities.
 
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::iter;
+use std::iter::Peekable;
+use std::mem;
+use std::ops::Range;
+
 // `StrExt` -------------------------------------------------------------------------------------------------
 
 /// An extension trait for strings.
@@ -32,8 +39,120 @@ pub trait StrExt {
   #[must_use]
   fn capitalize(&self) -> String;
 
+  /// Creates a new [`String`] by centering this string within `width` display columns, padding it on both
+  /// sides with `fill`.
+  ///
+  /// If the padding cannot be split evenly, the right side gets one more column. Otherwise, this works like
+  /// [`pad_right`](StrExt::pad_right).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("Größe".center(10, '*'), "**Größe***");
+  /// ```
+  #[must_use]
+  fn center(&self, width: usize, fill: char) -> String;
+
+  /// Returns the substring of this string for a range of [`char`] positions, or [`None`] if the range is out
+  /// of bounds or `range.start` > `range.end`.
+  ///
+  /// Unlike slicing by byte positions, this never panics. Since the [`char`]s must be counted from the start
+  /// of the string, this takes O(n) time, where n is `range.end`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("Grüße".char_slice(1..4), Some("rüß"));
+  /// assert_eq!("Grüße".char_slice(4..6), None);
+  /// ```
+  #[must_use]
+  fn char_slice(&self, range: Range<usize>) -> Option<&str>;
+
+  /// Creates a new [`String`] by replacing accented Latin characters in this string with their ASCII base
+  /// characters.
+  ///
+  /// This is meant for pragmatic tasks such as generating ASCII slugs from titles. It uses a built-in table
+  /// that covers the Latin-1 Supplement and Latin Extended-A blocks, e.g. `'é'` becomes `"e"`, `'Ü'`
+  /// becomes `"U"`, and ligatures and special letters are spelled out, e.g. `'ß'` becomes `"ss"` and `'Æ'`
+  /// becomes `"AE"`. It is not a full Unicode decomposition: combining marks, as well as characters not in
+  /// the table, e.g. `'€'` or Greek letters, are kept unchanged.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("Crème Brûlée".deaccent(), "Creme Brulee");
+  /// assert_eq!("Straße".deaccent(), "Strasse");
+  /// assert_eq!("5 €".deaccent(), "5 €");
+  /// ```
+  #[must_use]
+  fn deaccent(&self) -> String;
+
+  /// Creates a new [`String`] by removing the longest common leading whitespace from all lines of this
+  /// string.
+  ///
+  /// Lines that consist of whitespace only are ignored when determining the common prefix, and they are
+  /// emptied in the result. Whitespace [`char`]s are compared exactly, so a tab and spaces do not match.
+  /// Line terminators (`\n` or `\r\n`) are kept, including a trailing one.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("    fn main() {\n      run();\n    }\n".dedent(), "fn main() {\n  run();\n}\n");
+  /// ```
+  #[must_use]
+  fn dedent(&self) -> String;
+
+  /// Returns the number of columns this string takes up when displayed in a terminal.
+  ///
+  /// The width is the sum of the widths of all [`char`]s. If the `unicode_width` feature is enabled, the
+  /// width of each [`char`] is taken from the `unicode-width` crate. Otherwise, a built-in approximation is
+  /// used: Most East Asian wide characters and emoji take up two columns, combining marks, zero-width
+  /// characters, and control characters take up none, and all other [`char`]s take up one column.
+  ///
+  /// Either way, control characters, including tab characters, count as zero columns, so tabs should be
+  /// expanded beforehand. Grapheme clusters, e.g. emoji joined by zero-width joiners, are not taken into
+  /// account.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("äöü".display_width(), 3);
+  /// assert_eq!("e\u{301}".display_width(), 1); // `e` followed by a combining acute accent
+  /// assert_eq!("日本語".display_width(), 6);
+  /// ```
+  #[must_use]
+  fn display_width(&self) -> usize;
+
+  /// Returns this string with exactly one trailing `'\n'`.
+  ///
+  /// If this string already ends with exactly one `'\n'`, it is returned unchanged as [`Cow::Borrowed`].
+  /// Otherwise, a [`Cow::Owned`] string is returned in which multiple trailing `'\n'`s are replaced by a
+  /// single one, or a `'\n'` is appended if there is none. An empty string stays empty.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("line".ensure_trailing_newline(), "line\n");
+  /// assert_eq!("line\n\n".ensure_trailing_newline(), "line\n");
+  /// assert_eq!("".ensure_trailing_newline(), "");
+  /// ```
+  #[must_use]
+  fn ensure_trailing_newline(&self) -> Cow<'_, str>;
+
   /// Creates a new [`String`] by putting this string, which may be a multi-line string, into a fence that is
-  /// made up of `c` and `text_width` - 1 characters wide.
+  /// made up of `c` and `text_width` - 1 columns wide, see [`rule`].
   ///
   /// # Examples
   ///
@@ -45,6 +164,320 @@ pub trait StrExt {
   #[must_use]
   fn fence(&self, c: char, text_width: usize) -> String;
 
+  /// Creates a new [`String`] by putting this string into a fence like [`fence`](StrExt::fence), with
+  /// `title` embedded into the top row.
+  ///
+  /// The top row starts with `c`, followed by `title` surrounded by spaces, and is filled up with `c`. If
+  /// `title` is wider than `text_width` - 4 columns, it is truncated with
+  /// [`truncate_ellipsis`](StrExt::truncate_ellipsis). If `title` is empty or nothing of it fits, the result
+  /// is the same as with [`fence`](StrExt::fence).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!(
+  ///   "1st line\n2nd line".fence_titled('*', 12, "Log"),
+  ///   "* Log *****\n*\n* 1st line\n* 2nd line\n*\n***********"
+  /// );
+  /// ```
+  #[must_use]
+  fn fence_titled(&self, c: char, text_width: usize, title: &str) -> String;
+
+  /// Creates a new [`String`] by putting `prefix` in front of each line of this string that does not
+  /// consist of whitespace only.
+  ///
+  /// Line terminators (`\n` or `\r\n`) are kept, including a trailing one, and no line is added. To prefix
+  /// blank lines as well, use [`indent_all`](StrExt::indent_all).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("a\n\nb\n".indent("> "), "> a\n\n> b\n");
+  /// ```
+  #[must_use]
+  fn indent(&self, prefix: &str) -> String;
+
+  /// Creates a new [`String`] by putting `prefix` in front of each line of this string.
+  ///
+  /// This works like [`indent`](StrExt::indent), but blank lines are prefixed as well.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("a\n\nb\n".indent_all("> "), "> a\n> \n> b\n");
+  /// ```
+  #[must_use]
+  fn indent_all(&self, prefix: &str) -> String;
+
+  /// Checks if this string consists of ASCII characters only.
+  ///
+  /// This is the same as [`str::is_ascii`]. An empty string is considered ASCII-only.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert!("Creme Brulee".is_ascii_only());
+  /// assert!(!"Crème Brûlée".is_ascii_only());
+  /// ```
+  #[must_use]
+  fn is_ascii_only(&self) -> bool;
+
+  /// Compares this string with `other` in natural order.
+  ///
+  /// Both strings are compared run by run: Consecutive ASCII digits are compared by their numeric values,
+  /// while all other characters are compared one by one. So `"file2"` sorts before `"file10"`. Numbers may
+  /// be arbitrarily long. If two strings differ only in leading zeros, the one with fewer leading zeros is
+  /// sorted first.
+  ///
+  /// The comparison is case-sensitive. For a case-insensitive comparison, use
+  /// [`natural_cmp_ci`](StrExt::natural_cmp_ci).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// let mut vec = vec!["file10", "file2", "file1"];
+  /// vec.sort_by(|lhs, rhs| lhs.natural_cmp(rhs));
+  /// assert_eq!(vec, vec!["file1", "file2", "file10"]);
+  /// ```
+  #[must_use]
+  fn natural_cmp(&self, other: &str) -> Ordering;
+
+  /// Compares this string with `other` in natural order, ignoring case.
+  ///
+  /// This works like [`natural_cmp`](StrExt::natural_cmp), but both strings are converted to lowercase
+  /// before being compared.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::cmp::Ordering;
+  ///
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("File2".natural_cmp_ci("file10"), Ordering::Less);
+  /// assert_eq!("FILE2".natural_cmp_ci("file2"), Ordering::Equal);
+  /// ```
+  #[must_use]
+  fn natural_cmp_ci(&self, other: &str) -> Ordering;
+
+  /// Creates a new [`String`] by padding this string on the left with `fill` so that it takes up `width`
+  /// display columns, i.e. right-aligning it.
+  ///
+  /// This works like [`pad_right`](StrExt::pad_right), but the padding goes to the left.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("日本".pad_left(6, ' '), "  日本");
+  /// ```
+  #[must_use]
+  fn pad_left(&self, width: usize, fill: char) -> String;
+
+  /// Creates a new [`String`] by padding this string on the right with `fill` so that it takes up `width`
+  /// display columns, i.e. left-aligning it.
+  ///
+  /// Unlike padding with [`format!`], e.g. `{:<10}`, which counts [`char`]s, this is based on
+  /// [`display_width`](StrExt::display_width), so wide [`char`]s are aligned correctly in a terminal. If
+  /// `fill` is a wide [`char`], the result may be one column narrower than `width`. If this string is
+  /// already at least `width` columns wide, it is returned unchanged.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("日本".pad_right(6, '.'), "日本..");
+  /// assert_eq!("日本".pad_right(3, '.'), "日本");
+  /// ```
+  #[must_use]
+  fn pad_right(&self, width: usize, fill: char) -> String;
+
+  /// Splits this string into a prefix that fits within `width` display columns and the remainder.
+  ///
+  /// The string is never split inside a [`char`]. A wide [`char`], e.g. a CJK character taking up two
+  /// columns, that would straddle the boundary goes entirely to the remainder, so the prefix may be one
+  /// column narrower than `width`. Zero-width [`char`]s, e.g. combining marks, that directly follow the
+  /// prefix stay with it. For the width of a [`char`], see [`display_width`](StrExt::display_width).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("Grüße".split_at_width(3), ("Grü", "ße"));
+  /// assert_eq!("日本語".split_at_width(3), ("日", "本語"));
+  /// ```
+  #[must_use]
+  fn split_at_width(&self, width: usize) -> (&str, &str);
+
+  /// Creates a new [`String`] by removing all ANSI escape sequences from this string, e.g. the ones that
+  /// style the labels of [`process_error`](crate::process_error).
+  ///
+  /// Control Sequence Introducer (CSI) sequences such as `"\x1b[1;31m"`, which include Select Graphic
+  /// Rendition (SGR) sequences, are removed, as are Operating System Command (OSC) sequences such as
+  /// hyperlinks, and other escape sequences starting with `'\x1b'`. An incomplete sequence, e.g. at the end
+  /// of a truncated string, is removed up to where it ends.
+  ///
+  /// As escape sequences take up no columns in a terminal, this is useful before calling
+  /// [`display_width`](StrExt::display_width) on a styled string.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("\x1b[1;31merror\x1b[0m: Oops".strip_ansi(), "error: Oops");
+  /// ```
+  #[must_use]
+  fn strip_ansi(&self) -> String;
+
+  /// Creates a new [`String`] by converting this string to camel case, e.g. `myConfigValue`.
+  ///
+  /// The first word is lowercase, and each following word is capitalized. For how the string is split into
+  /// words, see [`to_snake_case`](StrExt::to_snake_case).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("My config value".to_camel_case(), "myConfigValue");
+  /// ```
+  #[must_use]
+  fn to_camel_case(&self) -> String;
+
+  /// Creates a new [`String`] by converting this string to kebab case, e.g. `my-config-value`.
+  ///
+  /// All words are lowercase and separated by `-`. For how the string is split into words, see
+  /// [`to_snake_case`](StrExt::to_snake_case).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("myConfigValue".to_kebab_case(), "my-config-value");
+  /// ```
+  #[must_use]
+  fn to_kebab_case(&self) -> String;
+
+  /// Creates a new [`String`] by converting this string to shouty snake case, e.g. `MY_CONFIG_VALUE`.
+  ///
+  /// All words are uppercase and separated by `_`. This is the usual style of environment-variable names.
+  /// For how the string is split into words, see [`to_snake_case`](StrExt::to_snake_case).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("my-config-value".to_shouty_snake_case(), "MY_CONFIG_VALUE");
+  /// ```
+  #[must_use]
+  fn to_shouty_snake_case(&self) -> String;
+
+  /// Creates a new [`String`] by converting this string to snake case, e.g. `my_config_value`.
+  ///
+  /// All words are lowercase and separated by `_`. The case conversions split a string into words as
+  /// follows:
+  ///
+  /// - Every [`char`] that is not alphanumeric, e.g. a space, `_`, or `-`, separates words and is dropped.
+  /// - A new word starts at an uppercase letter that follows a lowercase letter or a digit, e.g. `myValue`.
+  /// - Within a run of uppercase letters, a new word starts at the last letter if it is followed by a
+  ///   lowercase letter, e.g. `HTTPServer` is split into `HTTP` and `Server`.
+  ///
+  /// Letters are converted by their Unicode case mappings, so non-ASCII letters are preserved.
+  ///
+  /// | Input              | Snake Case          | Kebab Case          | Camel Case        | Shouty Snake Case
+  /// | :----------------- | :------------------ | :------------------ | :---------------- | :----------------
+  /// | `myConfigValue`    | `my_config_value`   | `my-config-value`   | `myConfigValue`   | `MY_CONFIG_VALUE`
+  /// | `My config value`  | `my_config_value`   | `my-config-value`   | `myConfigValue`   | `MY_CONFIG_VALUE`
+  /// | `HTTPServer`       | `http_server`       | `http-server`       | `httpServer`      | `HTTP_SERVER`
+  /// | `log_file-path`    | `log_file_path`     | `log-file-path`     | `logFilePath`     | `LOG_FILE_PATH`
+  /// | `Größe in Bytes`   | `größe_in_bytes`    | `größe-in-bytes`    | `größeInBytes`    | `GRÖSSE_IN_BYTES`
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("myConfigValue".to_snake_case(), "my_config_value");
+  /// assert_eq!("HTTPServer".to_snake_case(), "http_server");
+  /// ```
+  #[must_use]
+  fn to_snake_case(&self) -> String;
+
+  /// Creates a new [`String`] that fits within `max` display columns by truncating this string and
+  /// appending `…` if needed.
+  ///
+  /// This is the same as [`truncate_marker`](StrExt::truncate_marker) with `"…"` as the marker, except
+  /// that the result is always owned. If `max` is 0, an empty string is returned.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("Hello, world!".truncate_ellipsis(6), "Hello…");
+  /// assert_eq!("Hello".truncate_ellipsis(6), "Hello");
+  /// ```
+  #[must_use]
+  fn truncate_ellipsis(&self, max: usize) -> String;
+
+  /// Truncates this string so that it fits within `width` display columns, including `marker`.
+  ///
+  /// If this string is wider than `width`, it is cut so that the prefix plus `marker` takes up at most
+  /// `width` columns, and `marker` is appended. The marker may be wider than one column, e.g. `"..."`. The
+  /// prefix is determined by [`split_at_width`](StrExt::split_at_width), so the result may be one column
+  /// narrower than `width` if a wide [`char`] straddles the boundary. If `width` is smaller than the width
+  /// of `marker`, the marker itself is truncated to `width` columns.
+  ///
+  /// If this string fits within `width`, it is returned borrowed.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("Hello, world!".truncate_marker(8, "…"), "Hello, …");
+  /// assert_eq!("Hello, world!".truncate_marker(8, "..."), "Hello...");
+  /// assert_eq!("Hello".truncate_marker(8, "..."), "Hello");
+  /// ```
+  #[must_use]
+  fn truncate_marker(&self, width: usize, marker: &str) -> Cow<'_, str>;
+
+  /// Creates a new [`String`] that fits within `max` display columns by replacing the middle of this string
+  /// with `…` if needed.
+  ///
+  /// This is useful for long paths, where both the beginning and the end are meaningful. Of the columns
+  /// left beside the ellipsis, the end gets one more than the beginning if their number is odd. The string
+  /// is never split inside a [`char`], see [`split_at_width`](StrExt::split_at_width), so the result may be
+  /// narrower than `max` if wide [`char`]s straddle the boundaries. If `max` is 0, an empty string is
+  /// returned.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("/home/user/.config/app/config.toml".truncate_middle(24), "/home/user/…/config.toml");
+  /// assert_eq!("config.toml".truncate_middle(20), "config.toml");
+  /// ```
+  #[must_use]
+  fn truncate_middle(&self, max: usize) -> String;
+
   /// Creates a new [`String`] by converting the first [`char`] of this string to lowercase.
   ///
   /// # Examples
@@ -56,6 +489,44 @@ pub trait StrExt {
   /// ```
   #[must_use]
   fn uncapitalize(&self) -> String;
+
+  /// Creates a new [`String`] by appending a line break and a rule made up of `c` to this string.
+  ///
+  /// The rule is as wide as the widest line of this string, measured in display columns, see
+  /// [`display_width`](StrExt::display_width). Unlike [`rule`], the width is not reduced by one. If `c` is a
+  /// wide [`char`], it is repeated fewer times, so the rule may be one column narrower. This is a
+  /// lightweight alternative to [`fence`](StrExt::fence), e.g. for section headers.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("Übersicht".underline('='), "Übersicht\n=========");
+  /// ```
+  #[must_use]
+  fn underline(&self, c: char) -> String;
+
+  /// Creates a new [`String`] by word-wrapping this string so no line is wider than `width` display columns.
+  ///
+  /// Each line of this string is treated as a paragraph, so existing line breaks are preserved. Within a
+  /// paragraph, words are separated by whitespace and filled greedily into lines. Runs of whitespace are
+  /// collapsed to a single space, and leading and trailing whitespace is removed. A word is split only if it
+  /// alone is wider than `width`, see [`split_at_width`](StrExt::split_at_width). Even then, each line holds
+  /// at least one [`char`], so a `width` of 0 is treated like 1.
+  ///
+  /// To wrap text for a terminal, pass [`crate::TEXT_WIDTH`] - 1 as `width`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// assert_eq!("The quick brown fox".wrap(10), "The quick\nbrown fox");
+  /// assert_eq!("Donaudampfschiff fährt".wrap(8), "Donaudam\npfschiff\nfährt");
+  /// ```
+  #[must_use]
+  fn wrap(&self, width: usize) -> String;
 }
 
 impl StrExt for str {
@@ -71,10 +542,69 @@ impl StrExt for str {
     }
   }
 
+  fn center(&self, width: usize, fill: char) -> String {
+    let missing = width.saturating_sub(self.display_width());
+    format!("{}{self}{}", padding(fill, missing / 2), padding(fill, missing - missing / 2))
+  }
+
+  fn char_slice(&self, range: Range<usize>) -> Option<&str> {
+    if range.start > range.end {
+      return None;
+    }
+
+    // Byte positions of all `char`s, followed by the length
+    let mut positions = self.char_indices().map(|(i, _)| i).chain(iter::once(self.len()));
+    let start = positions.nth(range.start)?;
+    let end = if range.is_empty() { start } else { positions.nth(range.len() - 1)? };
+    Some(&self[start..end])
+  }
+
+  fn deaccent(&self) -> String {
+    let mut ret = String::with_capacity(self.len());
+    for c in self.chars() {
+      match deaccent_char(c) {
+        Some(val) => ret.push_str(val),
+        None => ret.push(c),
+      }
+    }
+    ret
+  }
+
+  fn dedent(&self) -> String {
+    let margin = self
+      .split_inclusive('\n')
+      .filter(|line| !line.trim().is_empty())
+      .map(|line| &line[..line.len() - line.trim_start().len()])
+      .reduce(common_prefix)
+      .unwrap_or("");
+
+    let mut ret = String::with_capacity(self.len());
+    for line in self.split_inclusive('\n') {
+      if line.trim().is_empty() {
+        ret.push_str(&line[line.trim_end_matches(['\r', '\n']).len()..]);
+      } else {
+        ret.push_str(&line[margin.len()..]);
+      }
+    }
+    ret
+  }
+
+  #[inline]
+  fn display_width(&self) -> usize { self.chars().map(char_width).sum() }
+
+  fn ensure_trailing_newline(&self) -> Cow<'_, str> {
+    let trimmed = self.trim_end_matches('\n');
+    if self.is_empty() || self.len() - trimmed.len() == 1 {
+      Cow::Borrowed(self)
+    } else {
+      Cow::Owned(format!("{trimmed}\n"))
+    }
+  }
+
   fn fence(&self, c: char, text_width: usize) -> String {
     let mut ret = String::new();
 
-    let row = c.to_string().repeat(text_width - 1);
+    let row = rule(c, text_width);
 
     ret.push_str(&row);
     ret.push('\n');
@@ -95,6 +625,147 @@ impl StrExt for str {
     ret
   }
 
+  fn fence_titled(&self, c: char, text_width: usize, title: &str) -> String {
+    let fence = self.fence(c, text_width);
+    let title = title.truncate_ellipsis(text_width.saturating_sub(4));
+    if title.is_empty() {
+      return fence;
+    }
+
+    let head = format!("{c} {title} ");
+    let fill_width = text_width.saturating_sub(1).saturating_sub(head.display_width());
+    let fill_count = fill_width / char_width(c).max(1);
+    let top_row_len = fence.find('\n').unwrap_or(fence.len());
+    format!("{head}{}{}", c.to_string().repeat(fill_count), &fence[top_row_len..])
+  }
+
+  #[inline]
+  fn indent(&self, prefix: &str) -> String { indent_impl(self, prefix, false) }
+
+  #[inline]
+  fn indent_all(&self, prefix: &str) -> String { indent_impl(self, prefix, true) }
+
+  #[inline]
+  fn is_ascii_only(&self) -> bool { self.is_ascii() }
+
+  #[inline]
+  fn natural_cmp(&self, other: &str) -> Ordering {
+    natural_cmp_impl(self.chars().peekable(), other.chars().peekable())
+  }
+
+  #[inline]
+  fn natural_cmp_ci(&self, other: &str) -> Ordering {
+    natural_cmp_impl(
+      self.chars().flat_map(char::to_lowercase).peekable(),
+      other.chars().flat_map(char::to_lowercase).peekable(),
+    )
+  }
+
+  fn pad_left(&self, width: usize, fill: char) -> String {
+    format!("{}{self}", padding(fill, width.saturating_sub(self.display_width())))
+  }
+
+  fn pad_right(&self, width: usize, fill: char) -> String {
+    format!("{self}{}", padding(fill, width.saturating_sub(self.display_width())))
+  }
+
+  fn split_at_width(&self, width: usize) -> (&str, &str) {
+    let mut remaining = width;
+    for (i, c) in self.char_indices() {
+      let Some(val) = remaining.checked_sub(char_width(c)) else {
+        return self.split_at(i);
+      };
+      remaining = val;
+    }
+    (self, "")
+  }
+
+  fn strip_ansi(&self) -> String {
+    let mut ret = String::with_capacity(self.len());
+    let mut chars = self.chars().peekable();
+    while let Some(c) = chars.next() {
+      match c {
+        '\x1b' => match chars.next() {
+          Some('[') => skip_csi(&mut chars),
+          Some(']') => {
+            // An OSC sequence is terminated by BEL or ST, i.e. `ESC \`
+            while let Some(c) = chars.next() {
+              if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                break;
+              }
+            }
+          }
+          Some('\x20'..='\x2f') => {
+            // Intermediate bytes, followed by a final byte
+            while chars.next_if(|c| ('\x20'..='\x2f').contains(c)).is_some() {}
+            chars.next();
+          }
+          _ => {} // Two-character sequence, or incomplete sequence at the end
+        },
+        '\u{9b}' => skip_csi(&mut chars),
+        _ => ret.push(c),
+      }
+    }
+    ret
+  }
+
+  fn to_camel_case(&self) -> String {
+    let mut ret = String::with_capacity(self.len());
+    for (i, word) in split_words(self).into_iter().enumerate() {
+      if i == 0 {
+        ret.push_str(&word.to_lowercase());
+      } else {
+        ret.push_str(&word.to_lowercase().capitalize());
+      }
+    }
+    ret
+  }
+
+  #[inline]
+  fn to_kebab_case(&self) -> String { split_words(self).join("-").to_lowercase() }
+
+  #[inline]
+  fn to_shouty_snake_case(&self) -> String { split_words(self).join("_").to_uppercase() }
+
+  #[inline]
+  fn to_snake_case(&self) -> String { split_words(self).join("_").to_lowercase() }
+
+  #[inline]
+  fn truncate_ellipsis(&self, max: usize) -> String { self.truncate_marker(max, "…").into_owned() }
+
+  fn truncate_marker(&self, width: usize, marker: &str) -> Cow<'_, str> {
+    if self.display_width() <= width {
+      return Cow::Borrowed(self);
+    }
+    match width.checked_sub(marker.display_width()) {
+      Some(prefix_width) => Cow::Owned(format!("{}{marker}", self.split_at_width(prefix_width).0)),
+      None => Cow::Owned(String::from(marker.split_at_width(width).0)),
+    }
+  }
+
+  fn truncate_middle(&self, max: usize) -> String {
+    if self.display_width() <= max {
+      return self.to_owned();
+    }
+    let Some(available) = max.checked_sub(1) else {
+      return String::new();
+    };
+
+    let head = self.split_at_width(available / 2).0;
+    let mut tail_width = 0;
+    let mut tail = "";
+    for (i, c) in self.char_indices().rev() {
+      tail_width += char_width(c);
+      if tail_width > available - available / 2 {
+        break;
+      }
+      tail = &self[i..];
+    }
+    // Zero-width `char`s belong to the `char` before them, which has been cut off
+    let tail = tail.trim_start_matches(|c| char_width(c) == 0);
+    format!("{head}…{tail}")
+  }
+
   fn uncapitalize(&self) -> String {
     let mut it = self.chars();
 
@@ -103,8 +774,269 @@ impl StrExt for str {
       Some(c) => c.to_lowercase().collect::<String>() + it.as_str(),
     }
   }
+
+  fn underline(&self, c: char) -> String {
+    let width = self.lines().map(str::display_width).max().unwrap_or(0);
+    format!("{self}\n{}", padding(c, width))
+  }
+
+  fn wrap(&self, width: usize) -> String {
+    let mut lines = Vec::new();
+    for paragraph in self.lines() {
+      let mut line = String::new();
+      let mut line_width = 0;
+      for mut word in paragraph.split_whitespace() {
+        let word_width = word.display_width();
+        if line_width > 0 && line_width + 1 + word_width <= width {
+          line.push(' ');
+          line.push_str(word);
+          line_width += 1 + word_width;
+          continue;
+        }
+        if line_width > 0 {
+          lines.push(mem::take(&mut line));
+        }
+
+        // Split words that are too wide on their own
+        while word.display_width() > width {
+          let (mut head, mut tail) = word.split_at_width(width);
+          if head.is_empty() {
+            let len = tail.chars().next().map_or(0, char::len_utf8);
+            (head, tail) = word.split_at(len);
+          }
+          if tail.is_empty() {
+            break; // A single `char` wider than `width`
+          }
+          lines.push(String::from(head));
+          word = tail;
+        }
+        line.push_str(word);
+        line_width = word.display_width();
+      }
+      lines.push(line);
+    }
+    lines.join("\n")
+  }
+}
+
+// Functions ------------------------------------------------------------------------------------------------
+
+/// Returns the number of display columns `c` takes up, see [`StrExt::display_width`].
+#[cfg(feature = "unicode_width")]
+fn char_width(c: char) -> usize { unicode_width::UnicodeWidthChar::width(c).unwrap_or(0) }
+
+/// Returns the number of display columns `c` takes up, see [`StrExt::display_width`].
+#[cfg(not(feature = "unicode_width"))]
+fn char_width(c: char) -> usize {
+  let c = u32::from(c);
+  let is_zero_width = matches!(c,
+    // Control characters
+    0..0x20 | 0x7f..0xa0
+    // Combining marks, zero-width characters, and variation selectors
+    | 0x0300..=0x036f | 0x1ab0..=0x1aff | 0x1dc0..=0x1dff | 0x200b..=0x200f | 0x20d0..=0x20ff
+    | 0xfe00..=0xfe0f | 0xfe20..=0xfe2f
+  );
+  // East Asian wide characters and emoji
+  let is_wide = matches!(c,
+    0x1100..=0x115f | 0x2e80..=0x303e | 0x3041..=0x33ff | 0x3400..=0x4dbf | 0x4e00..=0x9fff
+    | 0xa000..=0xa4cf | 0xac00..=0xd7a3 | 0xf900..=0xfaff | 0xfe30..=0xfe4f | 0xff00..=0xff60
+    | 0xffe0..=0xffe6 | 0x1f300..=0x1f64f | 0x1f900..=0x1f9ff | 0x20000..=0x2fffd | 0x30000..=0x3fffd
+  );
+  if is_zero_width {
+    0
+  } else if is_wide {
+    2
+  } else {
+    1
+  }
 }
 
+/// Returns the longest common prefix of `lhs` and `rhs`.
+fn common_prefix<'a>(lhs: &'a str, rhs: &str) -> &'a str {
+  let len = lhs
+    .char_indices()
+    .zip(rhs.chars())
+    .find(|&((_, l), r)| l != r)
+    .map_or(lhs.len().min(rhs.len()), |((i, _), _)| i);
+  &lhs[..len]
+}
+
+/// Returns the ASCII replacement for an accented Latin character, or [`None`] if `c` is not in the table.
+fn deaccent_char(c: char) -> Option<&'static str> {
+  let ret = match c {
+    'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+    'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+    'Æ' => "AE",
+    'æ' => "ae",
+    'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => "C",
+    'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+    'Ð' | 'Ď' | 'Đ' => "D",
+    'ð' | 'ď' | 'đ' => "d",
+    'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => "E",
+    'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+    'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => "G",
+    'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+    'Ĥ' | 'Ħ' => "H",
+    'ĥ' | 'ħ' => "h",
+    'Ì' | 'Í' | 'Î' | 'Ï' | 'Ĩ' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => "I",
+    'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+    'Ĳ' => "IJ",
+    'ĳ' => "ij",
+    'Ĵ' => "J",
+    'ĵ' => "j",
+    'Ķ' => "K",
+    'ķ' | 'ĸ' => "k",
+    'Ĺ' | 'Ļ' | 'Ľ' | 'Ŀ' | 'Ł' => "L",
+    'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+    'Ñ' | 'Ń' | 'Ņ' | 'Ň' | 'Ŋ' => "N",
+    'ñ' | 'ń' | 'ņ' | 'ň' | 'ŉ' | 'ŋ' => "n",
+    'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' | 'Ō' | 'Ŏ' | 'Ő' => "O",
+    'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+    'Œ' => "OE",
+    'œ' => "oe",
+    'Ŕ' | 'Ŗ' | 'Ř' => "R",
+    'ŕ' | 'ŗ' | 'ř' => "r",
+    'Ś' | 'Ŝ' | 'Ş' | 'Š' => "S",
+    'ś' | 'ŝ' | 'ş' | 'š' | 'ſ' => "s",
+    'ß' => "ss",
+    'Ţ' | 'Ť' | 'Ŧ' => "T",
+    'ţ' | 'ť' | 'ŧ' => "t",
+    'Þ' => "TH",
+    'þ' => "th",
+    'Ù' | 'Ú' | 'Û' | 'Ü' | 'Ũ' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' => "U",
+    'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+    'Ŵ' => "W",
+    'ŵ' => "w",
+    'Ý' | 'Ŷ' | 'Ÿ' => "Y",
+    'ý' | 'ÿ' | 'ŷ' => "y",
+    'Ź' | 'Ż' | 'Ž' => "Z",
+    'ź' | 'ż' | 'ž' => "z",
+    _ => return None,
+  };
+  Some(ret)
+}
+
+fn indent_impl(s: &str, prefix: &str, is_all: bool) -> String {
+  let mut ret = String::with_capacity(s.len());
+  for line in s.split_inclusive('\n') {
+    if is_all || !line.trim().is_empty() {
+      ret.push_str(prefix);
+    }
+    ret.push_str(line);
+  }
+  ret
+}
+
+fn natural_cmp_impl<L, R>(mut lhs: Peekable<L>, mut rhs: Peekable<R>) -> Ordering
+where
+  L: Iterator<Item = char>,
+  R: Iterator<Item = char>, {
+  // Used if the strings turn out to differ only in leading zeros
+  let mut tie = Ordering::Equal;
+
+  loop {
+    match (lhs.peek().copied(), rhs.peek().copied()) {
+      (None, None) => return tie,
+      (None, Some(_)) => return Ordering::Less,
+      (Some(_), None) => return Ordering::Greater,
+      (Some(l), Some(r)) if l.is_ascii_digit() && r.is_ascii_digit() => {
+        let l = take_digits(&mut lhs);
+        let r = take_digits(&mut rhs);
+        let l_trimmed = l.trim_start_matches('0');
+        let r_trimmed = r.trim_start_matches('0');
+        // Without leading zeros, the longer number is the greater one
+        let ord = l_trimmed.len().cmp(&r_trimmed.len()).then_with(|| l_trimmed.cmp(r_trimmed));
+        if ord != Ordering::Equal {
+          return ord;
+        }
+        if tie == Ordering::Equal {
+          tie = l.len().cmp(&r.len());
+        }
+      }
+      (Some(l), Some(r)) => {
+        let ord = l.cmp(&r);
+        if ord != Ordering::Equal {
+          return ord;
+        }
+        lhs.next();
+        rhs.next();
+      }
+    }
+  }
+}
+
+/// Returns a [`String`] made up of `fill` that is at most `width` display columns wide.
+fn padding(fill: char, width: usize) -> String { fill.to_string().repeat(width / char_width(fill).max(1)) }
+
+/// Creates a new [`String`] made up of `c` and `text_width` - 1 columns wide.
+///
+/// Like [`fence`](StrExt::fence), this takes a text width that includes the line break, so the rule fits
+/// into lines of `text_width` - 1 columns. If `c` is a wide [`char`], see
+/// [`display_width`](StrExt::display_width), it is repeated fewer times, so the rule may be one column
+/// narrower. If `text_width` is 0, an empty string is returned.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::str;
+///
+/// assert_eq!(str::rule('-', 6), "-----");
+/// assert_eq!(str::rule('═', 6), "═════");
+/// ```
+#[must_use]
+pub fn rule(c: char, text_width: usize) -> String {
+  c.to_string().repeat(text_width.saturating_sub(1) / char_width(c).max(1))
+}
+
+/// Skips the parameter, intermediate, and final bytes of a CSI sequence. A final byte is only skipped if
+/// present, so a truncated sequence ends before the first character that cannot belong to it.
+fn skip_csi<I: Iterator<Item = char>>(it: &mut Peekable<I>) {
+  while it.next_if(|c| ('\x20'..='\x3f').contains(c)).is_some() {}
+  it.next_if(|c| ('\x40'..='\x7e').contains(c));
+}
+
+/// Splits `s` into words for the case conversions, see [`StrExt::to_snake_case`].
+fn split_words(s: &str) -> Vec<&str> {
+  let chars: Vec<(usize, char)> = s.char_indices().collect();
+  let mut ret = Vec::new();
+  let mut start = None;
+  for (k, &(i, c)) in chars.iter().enumerate() {
+    if !c.is_alphanumeric() {
+      if let Some(start) = start.take() {
+        ret.push(&s[start..i]);
+      }
+      continue;
+    }
+    let Some(word_start) = start else {
+      start = Some(i);
+      continue;
+    };
+    if c.is_uppercase() {
+      // The previous `char` is alphanumeric since it belongs to the current word
+      let prev = chars[k - 1].1;
+      let is_next_lowercase = chars.get(k + 1).is_some_and(|&(_, next)| next.is_lowercase());
+      if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && is_next_lowercase) {
+        ret.push(&s[word_start..i]);
+        start = Some(i);
+      }
+    }
+  }
+  if let Some(start) = start {
+    ret.push(&s[start..]);
+  }
+  ret
+}
+
+fn take_digits<I: Iterator<Item = char>>(it: &mut Peekable<I>) -> String {
+  let mut ret = String::new();
+  while let Some(c) = it.next_if(char::is_ascii_digit) {
+    ret.push(c);
+  }
+  ret
+}
+
+// Tests ====================================================================================================
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -125,6 +1057,293 @@ mod tests {
     assert_eq!("€".capitalize(), "€");
   }
 
+  #[test]
+  fn test_str_ext_center() {
+    assert_eq!("ab".center(6, ' '), "  ab  ");
+    assert_eq!("ab".center(5, '-'), "-ab--");
+    assert_eq!("äöü".center(7, '*'), "**äöü**");
+    assert_eq!("日本".center(7, ' '), " 日本  ");
+    assert_eq!("abc".center(2, ' '), "abc");
+  }
+
+  #[test]
+  fn test_str_ext_char_slice() {
+    let s = "aäöü€b";
+    assert_eq!(s.char_slice(0..0), Some(""));
+    assert_eq!(s.char_slice(2..5), Some("öü€"));
+    assert_eq!(s.char_slice(0..6), Some(s));
+    assert_eq!(s.char_slice(6..6), Some(""));
+    assert_eq!(s.char_slice(5..7), None);
+    assert_eq!(s.char_slice(7..7), None);
+    #[allow(clippy::reversed_empty_ranges)]
+    let range = 3..2;
+    assert_eq!(s.char_slice(range), None);
+    assert_eq!("".char_slice(0..0), Some(""));
+  }
+
+  #[test]
+  fn test_str_ext_deaccent() {
+    assert_eq!("".deaccent(), "");
+    assert_eq!("Ärger über Öl in Łódź".deaccent(), "Arger uber Ol in Lodz");
+    assert_eq!("Æsir, Œuvre, Þorn, Weißbier".deaccent(), "AEsir, OEuvre, THorn, Weissbier");
+    assert_eq!("Čeština, Français, Español".deaccent(), "Cestina, Francais, Espanol");
+
+    // Characters outside the table are kept, including combining marks
+    assert_eq!("€ αβγ e\u{301}".deaccent(), "€ αβγ e\u{301}");
+    assert!("Ærøskøbing".deaccent().is_ascii_only());
+  }
+
+  #[test]
+  fn test_str_ext_dedent() {
+    assert_eq!("  a\n    b\n  c".dedent(), "a\n  b\nc");
+    assert_eq!("  a\n\n    b\n".dedent(), "a\n\n  b\n");
+    assert_eq!("  a\n \t \n  b\r\n".dedent(), "a\n\nb\r\n");
+    assert_eq!("a\n  b".dedent(), "a\n  b");
+    assert_eq!("".dedent(), "");
+    assert_eq!("   ".dedent(), "");
+
+    // Mixed tabs and spaces
+    assert_eq!("\t  a\n\t b\n".dedent(), " a\nb\n");
+    assert_eq!("\ta\n  b\n".dedent(), "\ta\n  b\n");
+  }
+
+  #[test]
+  fn test_str_ext_display_width() {
+    let cases = [
+      ("", 0, 0),
+      ("abc", 3, 3),
+      ("äöü", 3, 6),
+      ("a\u{308}o\u{308}", 2, 6), // Combining diaeresis
+      ("日本語", 6, 9),
+      ("🦀", 2, 4),
+      ("\t", 0, 1),
+      ("a\u{200b}b", 2, 5), // Zero-width space
+    ];
+    for (s, width, len) in cases {
+      assert_eq!(s.display_width(), width, "{s:?}");
+      assert_eq!(s.len(), len, "{s:?}");
+    }
+  }
+
+  #[test]
+  fn test_str_ext_ensure_trailing_newline() {
+    assert!(matches!("".ensure_trailing_newline(), Cow::Borrowed("")));
+    assert!(matches!("line\n".ensure_trailing_newline(), Cow::Borrowed("line\n")));
+    assert!(matches!("\n".ensure_trailing_newline(), Cow::Borrowed("\n")));
+
+    assert!(matches!("line".ensure_trailing_newline(), Cow::Owned(val) if val == "line\n"));
+    assert!(matches!("line\n\n\n".ensure_trailing_newline(), Cow::Owned(val) if val == "line\n"));
+    assert!(matches!("a\n\nb".ensure_trailing_newline(), Cow::Owned(val) if val == "a\n\nb\n"));
+    assert!(matches!("\n\n".ensure_trailing_newline(), Cow::Owned(val) if val == "\n"));
+  }
+
+  #[test]
+  fn test_str_ext_fence_titled() {
+    assert_eq!("text".fence_titled('#', 12, "Title"), "# Title ###\n#\n# text\n#\n###########");
+    assert_eq!("text".fence_titled('#', 10, "Title"), "# Title #\n#\n# text\n#\n#########");
+    assert_eq!("text".fence_titled('─', 10, "Größe"), "─ Größe ─\n─\n─ text\n─\n─────────");
+
+    // Long and empty titles
+    assert_eq!("text".fence_titled('#', 9, "Title"), "# Title \n#\n# text\n#\n########");
+    assert_eq!("text".fence_titled('#', 8, "Title"), "# Tit… \n#\n# text\n#\n#######");
+    assert_eq!("text".fence_titled('#', 6, "Title"), "# T… \n#\n# text\n#\n#####");
+    assert_eq!("text".fence_titled('#', 4, "Title"), "text".fence('#', 4));
+    assert_eq!("text".fence_titled('#', 12, ""), "text".fence('#', 12));
+  }
+
+  #[test]
+  fn test_str_ext_indent() {
+    assert_eq!("a\nb".indent("  "), "  a\n  b");
+    assert_eq!("a\n\n \nb\n".indent("\t"), "\ta\n\n \n\tb\n");
+    assert_eq!("a\r\nb\r\n".indent("# "), "# a\r\n# b\r\n");
+    assert_eq!("".indent("  "), "");
+  }
+
+  #[test]
+  fn test_str_ext_indent_all() {
+    assert_eq!("a\n\nb\n".indent_all("  "), "  a\n  \n  b\n");
+    assert_eq!("\n".indent_all("  "), "  \n");
+    assert_eq!("".indent_all("  "), "");
+  }
+
+  #[test]
+  fn test_str_ext_is_ascii_only() {
+    assert!("".is_ascii_only());
+    assert!("abc 123 ~".is_ascii_only());
+    assert!(!"abc 123 ä".is_ascii_only());
+  }
+
+  #[test]
+  fn test_str_ext_natural_cmp() {
+    let mut vec =
+      vec!["file10", "file2", "File3", "file1", "file02", "a10b2", "a10b10", "a9", "08", "10", "8"];
+    vec.sort_by(|lhs, rhs| lhs.natural_cmp(rhs));
+    assert_eq!(vec, vec![
+      "8", "08", "10", "File3", "a9", "a10b2", "a10b10", "file1", "file2", "file02", "file10"
+    ]);
+
+    assert_eq!("08".natural_cmp("10"), Ordering::Less);
+    assert_eq!("10".natural_cmp("08"), Ordering::Greater);
+    assert_eq!("".natural_cmp(""), Ordering::Equal);
+    assert_eq!("".natural_cmp("0"), Ordering::Less);
+    assert_eq!("x99999999999999999999999".natural_cmp("x100000000000000000000000"), Ordering::Less);
+  }
+
+  #[test]
+  fn test_str_ext_natural_cmp_ci() {
+    let mut vec = vec!["file10", "File2", "FILE1", "file3"];
+    vec.sort_by(|lhs, rhs| lhs.natural_cmp_ci(rhs));
+    assert_eq!(vec, vec!["FILE1", "File2", "file3", "file10"]);
+
+    assert_eq!("ÄB2".natural_cmp_ci("äb2"), Ordering::Equal);
+  }
+
+  #[test]
+  fn test_str_ext_pad_left() {
+    assert_eq!("ab".pad_left(4, ' '), "  ab");
+    assert_eq!("äöü".pad_left(5, '0'), "00äöü");
+    assert_eq!("日本".pad_left(5, ' '), " 日本");
+    assert_eq!("e\u{301}".pad_left(3, ' '), "  e\u{301}");
+    assert_eq!("abc".pad_left(0, ' '), "abc");
+  }
+
+  #[test]
+  fn test_str_ext_pad_right() {
+    assert_eq!("ab".pad_right(4, ' '), "ab  ");
+    assert_eq!("äöü".pad_right(5, '.'), "äöü..");
+    assert_eq!("日本".pad_right(5, ' '), "日本 ");
+    assert_eq!("ab".pad_right(7, '日'), "ab日日");
+    assert_eq!("abc".pad_right(2, ' '), "abc");
+  }
+
+  #[test]
+  fn test_str_ext_split_at_width() {
+    assert_eq!("abcdef".split_at_width(3), ("abc", "def"));
+    assert_eq!("abc".split_at_width(3), ("abc", ""));
+    assert_eq!("abc".split_at_width(10), ("abc", ""));
+    assert_eq!("abc".split_at_width(0), ("", "abc"));
+    assert_eq!("".split_at_width(0), ("", ""));
+
+    // Multi-byte and wide `char`s
+    assert_eq!("äöü€".split_at_width(2), ("äö", "ü€"));
+    assert_eq!("a日b".split_at_width(3), ("a日", "b"));
+    assert_eq!("a日b".split_at_width(2), ("a", "日b"));
+    assert_eq!("日本".split_at_width(1), ("", "日本"));
+    assert_eq!("🦀x".split_at_width(2), ("🦀", "x"));
+
+    // Zero-width `char`s stay with the prefix
+    assert_eq!("ae\u{301}b".split_at_width(2), ("ae\u{301}", "b"));
+    assert_eq!("\u{301}a".split_at_width(0), ("\u{301}", "a"));
+  }
+
+  #[test]
+  fn test_str_ext_strip_ansi() {
+    use owo_colors::OwoColorize;
+
+    use crate::macros::Style;
+
+    assert_eq!("plain".strip_ansi(), "plain");
+    assert_eq!("".strip_ansi(), "");
+
+    // Bold red label, as produced by `process_error!`
+    let label = format!("{}", "error".style(Style::new().bold().red()));
+    assert_ne!(label, "error");
+    assert_eq!(label.strip_ansi(), "error");
+    assert_eq!(format!("app: {label}: Oops").strip_ansi(), "app: error: Oops");
+    assert_eq!("\x1b[1m\x1b[31merror\x1b[0m".strip_ansi(), "error");
+    assert_eq!("\x1b[1m\x1b[31mäöü\x1b[0m".strip_ansi().display_width(), 3);
+
+    // Other sequences
+    assert_eq!("a\x1b[2Kb\x1b[?25lc".strip_ansi(), "abc");
+    assert_eq!("\u{9b}1mbold".strip_ansi(), "bold");
+    assert_eq!("\x1b]8;;https://example.com\x07link\x1b]8;;\x1b\\".strip_ansi(), "link");
+    assert_eq!("a\x1b(Bb\x1bMc".strip_ansi(), "abc");
+
+    // Incomplete sequences
+    assert_eq!("error\x1b".strip_ansi(), "error");
+    assert_eq!("error\x1b[1;3".strip_ansi(), "error");
+    assert_eq!("error\x1b[1;3\nnext".strip_ansi(), "error\nnext");
+    assert_eq!("error\x1b]8;;https://ex".strip_ansi(), "error");
+  }
+
+  #[test]
+  fn test_str_ext_to_camel_case() {
+    assert_eq!("my config value".to_camel_case(), "myConfigValue");
+    assert_eq!("MyConfigValue".to_camel_case(), "myConfigValue");
+    assert_eq!("HTTPServer".to_camel_case(), "httpServer");
+    assert_eq!("__über_größe__".to_camel_case(), "überGröße");
+    assert_eq!("".to_camel_case(), "");
+  }
+
+  #[test]
+  fn test_str_ext_to_kebab_case() {
+    assert_eq!("myConfigValue".to_kebab_case(), "my-config-value");
+    assert_eq!("parseHTTPResponse".to_kebab_case(), "parse-http-response");
+    assert_eq!("  Log  file  ".to_kebab_case(), "log-file");
+  }
+
+  #[test]
+  fn test_str_ext_to_shouty_snake_case() {
+    assert_eq!("myConfigValue".to_shouty_snake_case(), "MY_CONFIG_VALUE");
+    assert_eq!("tracing-config debug".to_shouty_snake_case(), "TRACING_CONFIG_DEBUG");
+    assert_eq!("Größe".to_shouty_snake_case(), "GRÖSSE");
+  }
+
+  #[test]
+  fn test_str_ext_to_snake_case() {
+    assert_eq!("myConfigValue".to_snake_case(), "my_config_value");
+    assert_eq!("HTTPServer".to_snake_case(), "http_server");
+    assert_eq!("XMLHttpRequest".to_snake_case(), "xml_http_request");
+    assert_eq!("APIKey2FA".to_snake_case(), "api_key2_fa");
+    assert_eq!("v2Beta".to_snake_case(), "v2_beta");
+    assert_eq!("ALL_CAPS".to_snake_case(), "all_caps");
+    assert_eq!("log_file-path.toml".to_snake_case(), "log_file_path_toml");
+    assert_eq!("ÄrgerÜberÖl".to_snake_case(), "ärger_über_öl");
+    assert_eq!("---".to_snake_case(), "");
+  }
+
+  #[test]
+  fn test_str_ext_truncate_ellipsis() {
+    assert_eq!("abcdef".truncate_ellipsis(6), "abcdef");
+    assert_eq!("abcdef".truncate_ellipsis(5), "abcd…");
+    assert_eq!("äöüäöü".truncate_ellipsis(3), "äö…");
+    assert_eq!("日本語".truncate_ellipsis(4), "日…");
+    assert_eq!("abcdef".truncate_ellipsis(1), "…");
+    assert_eq!("abcdef".truncate_ellipsis(0), "");
+    assert_eq!("".truncate_ellipsis(0), "");
+  }
+
+  #[test]
+  fn test_str_ext_truncate_marker() {
+    assert!(matches!("abcdef".truncate_marker(6, "…"), Cow::Borrowed("abcdef")));
+    assert!(matches!("".truncate_marker(0, "…"), Cow::Borrowed("")));
+
+    assert_eq!("abcdef".truncate_marker(5, "…"), "abcd…");
+    assert_eq!("abcdef".truncate_marker(5, "..."), "ab...");
+    assert_eq!("äöüäöü".truncate_marker(4, "..."), "ä...");
+    assert_eq!("日本語".truncate_marker(5, "…"), "日本…");
+    assert_eq!("日本語".truncate_marker(4, "…"), "日…");
+
+    // Width too small for the marker
+    assert_eq!("abcdef".truncate_marker(3, "..."), "...");
+    assert_eq!("abcdef".truncate_marker(2, "..."), "..");
+    assert_eq!("abcdef".truncate_marker(0, "…"), "");
+  }
+
+  #[test]
+  fn test_str_ext_truncate_middle() {
+    assert_eq!("abcdef".truncate_middle(6), "abcdef");
+    assert_eq!("abcdefgh".truncate_middle(6), "ab…fgh");
+    assert_eq!("abcdefgh".truncate_middle(5), "ab…gh");
+    assert_eq!("äöüäöü".truncate_middle(4), "ä…öü");
+    assert_eq!("日本語日本語".truncate_middle(8), "日…本語");
+    assert_eq!("日本語日本語".truncate_middle(7), "日…語");
+    assert_eq!("ae\u{301}e\u{301}e\u{301}".truncate_middle(3), "a…e\u{301}");
+    assert_eq!("abc".truncate_middle(1), "…");
+    assert_eq!("abc".truncate_middle(0), "");
+    assert_eq!("".truncate_middle(0), "");
+  }
+
   #[test]
   fn test_str_ext_uncapitalize() {
     assert_eq!("".uncapitalize(), "");
@@ -132,6 +1351,51 @@ mod tests {
     assert_eq!("Äöü".uncapitalize(), "äöü");
     assert_eq!("€".uncapitalize(), "€");
   }
+
+  #[test]
+  fn test_str_ext_underline() {
+    assert_eq!("".underline('-'), "\n");
+    assert_eq!("Title".underline('-'), "Title\n-----");
+    assert_eq!("Größe €".underline('='), "Größe €\n=======");
+    assert_eq!("a\nbcd\nef".underline('~'), "a\nbcd\nef\n~~~");
+    assert_eq!("日本".underline('-'), "日本\n----");
+    assert_eq!("日本".underline('＝'), "日本\n＝＝");
+    assert_eq!("Title".underline('＝'), "Title\n＝＝");
+  }
+
+  #[test]
+  fn test_str_ext_wrap() {
+    assert_eq!("The quick brown fox jumps".wrap(9), "The quick\nbrown fox\njumps");
+    assert_eq!("The quick brown fox jumps".wrap(100), "The quick brown fox jumps");
+    assert_eq!("".wrap(10), "");
+
+    // Long words
+    assert_eq!("a abcdefghij b".wrap(4), "a\nabcd\nefgh\nij b");
+    assert_eq!("äöüäöü".wrap(4), "äöüä\nöü");
+    assert_eq!("日本語".wrap(4), "日本\n語");
+    assert_eq!("日本".wrap(1), "日\n本");
+    assert_eq!("ab".wrap(0), "a\nb");
+
+    // Whitespace
+    assert_eq!("  one   two  \t".wrap(7), "one two");
+    assert_eq!("one two   ".wrap(3), "one\ntwo");
+
+    // Embedded newlines
+    assert_eq!("one two\n\nthree four\n".wrap(5), "one\ntwo\n\nthree\nfour");
+    assert_eq!("a b\r\nc d".wrap(10), "a b\nc d");
+  }
+
+  // Functions ----------------------------------------------------------------------------------------------
+
+  #[test]
+  fn test_rule() {
+    assert_eq!(rule('-', 10), "---------");
+    assert_eq!(rule('─', 3), "──");
+    assert_eq!(rule('-', 1), "");
+    assert_eq!(rule('-', 0), "");
+    assert_eq!(rule('日', 6), "日日");
+    assert_eq!(rule('日', 2), "");
+  }
 }
 
 // EOF
@@ -3,46 +3,87 @@
 //! Initialize `tracing` using a TOML file.
 //!
 //! It is a wrapper around the [`tracing_config`] crate, using Meadows's configuration-file search from
-//! [`crate::config`].
+//! [`crate::config`]. Configuration files may also be written in YAML or JSON, see [`ConfigFormat`].
 //!
 //! For binary executables, use the [`try_init`] function. For example and test executables, use the [`init`]
 //! function.
 
+use std::error::Error;
 use std::ffi::OsString;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
 use std::fmt::Write;
+use std::fs;
 use std::io;
 use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
 use std::sync::OnceLock;
+use std::sync::PoisonError;
+use std::time::Duration;
 
 use thiserror::Error as ThisError;
+use tracing::field;
 use tracing::info;
 use tracing_config;
 use tracing_config::TracingConfigError;
 use tracing_config::config::ArcMutexGuard;
+use tracing_config::config::model::TracingConfig;
 
 use crate::config::FindError;
 use crate::prelude::*;
 use crate::process::ExecType;
 use crate::process_note;
 
+static CURRENT_FILTER: Mutex<Option<String>> = Mutex::new(None);
+
 // `Config` -------------------------------------------------------------------------------------------------
 
 /// This structs holds the configuration used to initialize `tracing`.
-#[derive(Debug)]
+///
+/// Two environment variables are read when `tracing` is initialized, not when the [`Config`] is created:
+///
+/// | Field           | Default Name           | Effect
+/// | :-------------- | :--------------------- | :-----
+/// | `env_var`       | `tracing_config`       | Used as `paths` if `paths` is [`None`]
+/// | `debug_env_var` | `tracing_config_debug` | Enables debug mode if set to a true value as accepted by [`crate::env::get_bool`]
+///
+/// Hence, an explicit `paths` value takes precedence over the variable, whereas debug mode is enabled if
+/// either `is_debug` is `true` or the variable is set to a true value. A field that is [`None`] falls back
+/// to the default name. Setting the fields allows each executable to use its own variables, e.g.
+/// `MYAPP_TRACING_CONFIG`.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Debug)]
 pub struct Config {
+  /// The name of the environment variable that enables debug mode. If [`None`], `tracing_config_debug` is
+  /// used.
+  pub debug_env_var: Option<String>,
+  /// The name of the environment variable that holds the paths to search if `paths` is [`None`]. If
+  /// [`None`], `tracing_config` is used.
+  pub env_var: Option<String>,
   /// The executable type.
   pub exec_type: ExecType,
-  /// If `true`, debug mode is enabled.
+  /// The pattern of the log-configuration file name to search for. `{}` is replaced with `name`, see
+  /// [`crate::config::find_config_file`].
+  pub file_name_pattern: String,
+  /// The format of the log-configuration file, which determines the parser used. When changing the format,
+  /// change the extension in `file_name_pattern` accordingly.
+  pub format: ConfigFormat,
+  /// If `true`, debug mode is enabled. Debug mode is enabled by `debug_env_var` as well.
   pub is_debug: bool,
-  /// If `true`, a process-start message is logged.
+  /// If `true`, a process-start message is logged, see [`StartInfo`].
   pub log_start: bool,
-  /// The name to search `{}tracing.toml` with.
+  /// The name to search `file_name_pattern` with.
   pub name: OsString,
   /// One or more paths, separated by the system-dependent path separator. Each path may point to a file or
-  /// directory.
+  /// directory. If [`None`], the value of `env_var` is used.
   pub paths: Option<OsString>,
   /// If `true`, the path of the loaded log-configuration file is printed to `stdout`.
   pub print_path: bool,
+  /// If `true`, the process-start message is logged as structured fields, see [`StartInfo::log_fields`].
+  /// Otherwise, it is logged as fenced text, see [`StartInfo::to_fenced_text`].
+  pub structured_start: bool,
   /// This hint is used to format the process-start message.
   pub text_width: usize,
 }
@@ -50,37 +91,174 @@ pub struct Config {
 impl Config {
   /// Returns a new [`Config`] with default settings suitable for the `exec_type`.
   ///
-  /// | Field        | Default Value
-  /// | :----------- | :------------
-  /// | `is_debug`   | `true` if environment variable `tracing_config_debug` is set to to `true`
-  /// | `log_start`  | `true`
-  /// | `name`       | Depends on `exec_type`
-  /// | `paths`      | The value of the environment variable `tracing_config`, otherwise [`None`]
-  /// | `print_path` | `true`
-  /// | `text_width` | [`crate::TEXT_WIDTH`]
+  /// | Field               | Default Value
+  /// | :------------------ | :------------
+  /// | `debug_env_var`     | [`None`], i.e. `tracing_config_debug`
+  /// | `env_var`           | [`None`], i.e. `tracing_config`
+  /// | `file_name_pattern` | `"{}tracing.toml"`
+  /// | `format`            | [`ConfigFormat::Toml`]
+  /// | `is_debug`          | `false`
+  /// | `log_start`         | `true`
+  /// | `name`              | Depends on `exec_type`
+  /// | `paths`             | [`None`]
+  /// | `print_path`        | `true`
+  /// | `structured_start`  | `false`
+  /// | `text_width`        | [`crate::text_width`]
+  ///
+  /// The environment variables are not read here, but when `tracing` is initialized, see [`Config`].
   #[must_use]
   pub fn new(exec_type: ExecType) -> Config {
-    use ExecType::*;
-
-    let is_debug = get_env_debug().unwrap_or(false);
-    let name = match exec_type {
-      Binary => crate::env::inv_name(),
-      Example => crate::env::name(),
-      DocTest | UnitTest | IntegTest | BenchTest => crate::env::test_name(),
-    };
-    let paths = get_env();
+    let name = crate::config::default_name(exec_type);
     Config {
+      debug_env_var: None,
+      env_var: None,
       exec_type,
-      is_debug,
+      file_name_pattern: String::from("{}tracing.toml"),
+      format: ConfigFormat::default(),
+      is_debug: false,
       log_start: true,
       name: name.clone(),
-      paths,
+      paths: None,
       print_path: true,
-      text_width: crate::TEXT_WIDTH,
+      structured_start: false,
+      text_width: crate::text_width(),
+    }
+  }
+
+  /// Validates this configuration.
+  ///
+  /// The following rules are checked:
+  ///
+  /// | Field               | Rule                | [`ConfigProblem`]
+  /// | :------------------ | :------------------ | :----------------
+  /// | `file_name_pattern` | Must contain `{}`   | [`MissingPlaceholder`]
+  /// | `name`              | Must not be empty   | [`EmptyName`]
+  /// | `text_width`        | Must be at least 2  | [`TextWidthTooSmall`]
+  ///
+  /// [`init`] and [`try_init`] call this function before searching for a configuration file.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Err`] with a [`ConfigValidationError`] listing all problems found.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::process::ExecType;
+  /// use meadows::tracing::config::Config;
+  ///
+  /// let mut config = Config::new(ExecType::Binary);
+  /// assert!(config.validate().is_ok());
+  ///
+  /// config.text_width = 0;
+  /// assert!(config.validate().is_err());
+  /// ```
+  ///
+  /// [`EmptyName`]: ConfigProblem::EmptyName
+  /// [`MissingPlaceholder`]: ConfigProblem::MissingPlaceholder
+  /// [`TextWidthTooSmall`]: ConfigProblem::TextWidthTooSmall
+  pub fn validate(&self) -> Result<(), ConfigValidationError> {
+    let mut problems = Vec::new();
+    if !self.file_name_pattern.contains("{}") {
+      problems.push(ConfigProblem::MissingPlaceholder(self.file_name_pattern.clone()));
+    }
+    if self.name.is_empty() {
+      problems.push(ConfigProblem::EmptyName);
+    }
+    if self.text_width < 2 {
+      problems.push(ConfigProblem::TextWidthTooSmall(self.text_width));
+    }
+    if problems.is_empty() { Ok(()) } else { Err(ConfigValidationError { problems }) }
+  }
+}
+
+// `ConfigFormat` -------------------------------------------------------------------------------------------
+
+/// The format of a log-configuration file, see [`Config::format`].
+///
+/// [`tracing_config`] expects TOML. Files in other formats are parsed into a TOML table in memory, which is
+/// then processed like the contents of a TOML file. No temporary files are written.
+///
+/// As TOML has no null value, YAML and JSON files must not contain null values.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ConfigFormat {
+  /// JSON.
+  Json,
+  /// TOML.
+  #[default]
+  Toml,
+  /// YAML.
+  Yaml,
+}
+
+impl ConfigFormat {
+  /// Returns the usual file extension of this format, without the leading dot.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::process::ExecType;
+  /// use meadows::tracing::config::Config;
+  /// use meadows::tracing::config::ConfigFormat;
+  ///
+  /// let format = ConfigFormat::Yaml;
+  /// let config = Config {
+  ///   file_name_pattern: format!("{{}}tracing.{}", format.extension()),
+  ///   format,
+  ///   ..Config::new(ExecType::Binary)
+  /// };
+  /// assert_eq!(config.file_name_pattern, "{}tracing.yaml");
+  /// ```
+  #[must_use]
+  pub fn extension(self) -> &'static str {
+    match self {
+      ConfigFormat::Json => "json",
+      ConfigFormat::Toml => "toml",
+      ConfigFormat::Yaml => "yaml",
     }
   }
 }
 
+impl Display for ConfigFormat {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      ConfigFormat::Json => "JSON",
+      ConfigFormat::Toml => "TOML",
+      ConfigFormat::Yaml => "YAML",
+    };
+    f.write_str(name)
+  }
+}
+
+// `ConfigProblem` ------------------------------------------------------------------------------------------
+
+/// A problem found by [`Config::validate`].
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+pub enum ConfigProblem {
+  /// `name` is empty.
+  #[error("`name` is empty")]
+  EmptyName,
+  /// `file_name_pattern` does not contain `{}`, which is replaced with `name`.
+  #[error("`file_name_pattern` ({0:?}) does not contain `{{}}`")]
+  MissingPlaceholder(String),
+  /// `text_width` is less than 2.
+  #[error("`text_width` ({0}) is less than 2")]
+  TextWidthTooSmall(usize),
+}
+
+// `ConfigValidationError` ----------------------------------------------------------------------------------
+
+/// Error type for [`Config::validate`].
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+#[error(
+  "Invalid configuration: {}",
+  .problems.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+)]
+pub struct ConfigValidationError {
+  /// The problems found, in the order the rules are checked.
+  pub problems: Vec<ConfigProblem>,
+}
+
 // `InitError` ----------------------------------------------------------------------------------------------
 
 /// Error type for [`init`]  and [`try_init`].
@@ -92,9 +270,21 @@ pub enum InitError {
   /// [`io::Error`].
   #[error("I/O error")]
   Io(#[from] io::Error),
+  /// The log-configuration file cannot be parsed, or it does not describe a valid configuration, see
+  /// [`ConfigFormat`].
+  #[error("Cannot parse {format} configuration file")]
+  Parse {
+    /// The format of the file.
+    format: ConfigFormat,
+    /// The underlying error.
+    source: Box<dyn Error + Send + Sync>,
+  },
   /// [`TracingConfigError`].
   #[error("Cannot configure `tracing`")]
   TracingConfig(#[from] TracingConfigError),
+  /// [`ConfigValidationError`].
+  #[error("Invalid configuration")]
+  Validation(#[from] ConfigValidationError),
 }
 
 impl InitError {
@@ -108,17 +298,182 @@ impl InitError {
   }
 }
 
+// `StartInfo` ----------------------------------------------------------------------------------------------
+
+/// Information about the process start, logged by [`init`] and [`try_init`] if [`Config::log_start`] is set.
+///
+/// Fields that are [`None`] or empty are omitted from the output. To control which fields are logged, clear
+/// [`Config::log_start`], collect a [`StartInfo`] after initialization, clear the unwanted fields, and log
+/// it yourself.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use meadows::tracing::config::StartInfo;
+///
+/// let mut info = StartInfo::collect(Path::new("tracing.toml"));
+/// info.args.clear();
+/// info.uptime = None;
+/// assert!(info.to_text().starts_with("Process started: "));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StartInfo {
+  /// The process arguments, excluding the invocation path.
+  pub args: Vec<String>,
+  /// The path of the loaded log-configuration file.
+  pub config_path: Option<PathBuf>,
+  /// The current directory.
+  pub current_dir: Option<PathBuf>,
+  /// The invocation name, see [`crate::env::inv_name`].
+  pub inv_name: String,
+  /// The invocation path, see [`crate::env::inv_path`].
+  pub inv_path: Option<PathBuf>,
+  /// The path of the executable, see [`crate::env::path`].
+  pub path: Option<PathBuf>,
+  /// The process uptime, see [`crate::process::uptime`].
+  pub uptime: Option<Duration>,
+}
+
+impl StartInfo {
+  /// Collects the information about the current process, using `config_path` as the path of the loaded
+  /// log-configuration file.
+  ///
+  /// `current_dir` is [`None`] if the current directory cannot be determined.
+  #[must_use]
+  pub fn collect(config_path: &Path) -> StartInfo {
+    StartInfo {
+      args: std::env::args().skip(1).collect(),
+      config_path: Some(config_path.to_path_buf()),
+      current_dir: std::env::current_dir().ok(),
+      inv_name: crate::env::inv_name().to_string_lossy().into_owned(),
+      inv_path: Some(crate::env::inv_path().clone()),
+      path: Some(crate::env::path().clone()),
+      uptime: Some(crate::process::uptime()),
+    }
+  }
+
+  /// Logs this information as structured `tracing` fields at level `INFO`.
+  ///
+  /// Fields that are [`None`] are not recorded. `args` is recorded only if not empty.
+  pub fn log_fields(&self) {
+    info!(
+      inv_name = %self.inv_name,
+      config_path = self.config_path.as_deref().map(Path::display).map(field::display),
+      current_dir = self.current_dir.as_deref().map(Path::display).map(field::display),
+      inv_path = self.inv_path.as_deref().map(Path::display).map(field::display),
+      path = self.path.as_deref().map(Path::display).map(field::display),
+      uptime = self.uptime.map(field::debug),
+      args = (!self.args.is_empty()).then(|| field::debug(&self.args)),
+      "Process started"
+    );
+  }
+
+  /// Returns this information as fenced text, see [`StrExt::fence`].
+  #[must_use]
+  pub fn to_fenced_text(&self, text_width: usize) -> String { self.to_text().fence('#', text_width) }
+
+  /// Returns this information as text, without a trailing newline.
+  #[must_use]
+  pub fn to_text(&self) -> String {
+    let mut ret = format!("Process started: {}\n", self.inv_name);
+
+    // Log-configuration file
+
+    if let Some(config_path) = &self.config_path {
+      writeln!(ret, "\nLog-configuration file: {config_path:?}").unwrap();
+    }
+
+    // Paths and uptime
+
+    let mut lines = String::new();
+    if let Some(current_dir) = &self.current_dir {
+      writeln!(lines, "Current directory: {current_dir:?}").unwrap();
+    }
+    if let Some(inv_path) = &self.inv_path {
+      writeln!(lines, "Invocation path  : {inv_path:?}").unwrap();
+    }
+    if let Some(path) = &self.path {
+      writeln!(lines, "Path             : {path:?}").unwrap();
+    }
+    if let Some(uptime) = &self.uptime {
+      writeln!(lines, "Uptime           : {uptime:.3?}").unwrap();
+    }
+    if !lines.is_empty() {
+      ret.push('\n');
+      ret.push_str(&lines);
+    }
+
+    // Arguments, if any
+
+    if !self.args.is_empty() {
+      ret.push_str("\nArguments:\n\n");
+      for arg in &self.args {
+        writeln!(ret, "- {arg:?}").unwrap();
+      }
+    }
+
+    ret.pop(); // Strip trailing '\n'
+    ret
+  }
+}
+
 // Functions ------------------------------------------------------------------------------------------------
 
-fn get_env() -> Option<OsString> { crate::env::get("tracing_config") }
+/// Returns the filter directives installed by [`init`] or [`try_init`], e.g. `"trace,my_crate=warn"`.
+///
+/// The directives are built from the `[filter.root]` table of the loaded log-configuration file: its
+/// `level`, if any, followed by its `directives`, all separated by commas. References to environment
+/// variables of the form `${env:NAME}` are resolved.
+///
+/// The result reflects the state at initialization time. Returns [`None`] if `tracing` has not been
+/// initialized successfully, or if the loaded file does not define a root filter.
+///
+/// # Examples
+///
+/// ```
+/// use meadows::process::ExecType;
+/// use meadows::tracing::config;
+///
+/// # #[cfg(not(miri))]
+/// # {
+/// config::init(&config::Config::new(ExecType::DocTest));
+/// if let Some(filter) = config::current_filter() {
+///   println!("Filter: {filter}");
+/// }
+/// # }
+/// ```
+#[must_use]
+pub fn current_filter() -> Option<String> {
+  CURRENT_FILTER.lock().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+/// Returns the root filter directives of a parsed log-configuration file. See [`current_filter`].
+fn filter_directives(value: &toml::Value) -> Option<String> {
+  let root = value.get("filter")?.get("root")?;
+
+  let level = root.get("level").and_then(toml::Value::as_str);
+  let directives = root.get("directives").and_then(toml::Value::as_array);
+  let directives: Vec<&str> = level
+    .into_iter()
+    .chain(directives.into_iter().flatten().filter_map(toml::Value::as_str))
+    .collect();
+  if directives.is_empty() { None } else { Some(directives.join(",")) }
+}
+
+fn get_env(config: &Config) -> Option<OsString> {
+  crate::env::get(config.env_var.as_deref().unwrap_or("tracing_config"))
+}
 
-fn get_env_debug() -> Option<bool> { crate::env::get("tracing_config_debug").map(|val| val == "true") }
+fn get_env_debug(config: &Config) -> Option<bool> {
+  crate::env::get_bool(config.debug_env_var.as_deref().unwrap_or("tracing_config_debug"))
+}
 
 fn init_file(config: &Config, file: &Path) -> Result<ArcMutexGuard, InitError> {
   // Read configuration
 
-  let tracing_config =
-    tracing_config::config::read_config(file, tracing_config::config::RESOLVE_FROM_ENV_DEPTH)?;
+  let (tracing_config, filter) = read_config(file, config.format)?;
 
   if config.print_path {
     process_note!(crate::io::stdout(), "Loaded configuration file `{}` titled {:?}", file.display(), tracing_config.title)?;
@@ -128,8 +483,14 @@ fn init_file(config: &Config, file: &Path) -> Result<ArcMutexGuard, InitError> {
 
   match tracing_config::config::init_config(config.is_debug, &tracing_config) {
     Ok(guard) => {
+      set_current_filter(filter);
       if config.log_start {
-        info!("\n{}", start_message(config, file));
+        let start_info = StartInfo::collect(file);
+        if config.structured_start {
+          start_info.log_fields();
+        } else {
+          info!("\n{}", start_info.to_fenced_text(config.text_width));
+        }
       }
       Ok(guard)
     }
@@ -182,47 +543,54 @@ pub fn init(config: &Config) -> &'static ArcMutexGuard {
   VAL.get_or_init(|| {
     assert!(config.exec_type != ExecType::Binary);
     match try_init_impl(config) {
-      Ok(guard) => guard,
+      Ok((guard, _)) => guard,
       Err(err) => panic!("{:?}", anyhow::Error::from(err).context("Cannot initialize logging")),
     }
   })
 }
 
-fn start_message(config: &Config, config_path: &Path) -> String {
-  let mut ret = String::new();
-
-  // `Process started`
-
-  let inv_name = crate::env::inv_name().to_string_lossy();
-  let current_dir_str = match std::env::current_dir() {
-    Ok(dir) => format!("{dir:?}"),
-    Err(_) => String::from("N/A"),
+/// Parses the text of a log-configuration file into a TOML table, as expected by [`tracing_config`].
+fn parse_config(text: &str, format: ConfigFormat) -> Result<toml::Value, Box<dyn Error + Send + Sync>> {
+  let table: toml::Table = match format {
+    ConfigFormat::Json => serde_json::from_str(text)?,
+    ConfigFormat::Toml => toml::from_str(text)?,
+    ConfigFormat::Yaml => serde_yaml_ng::from_str(text)?,
   };
-  let inv_path = crate::env::inv_path();
-  let path = crate::env::path();
-
-  write!(ret, "\
-Process started: {inv_name}
-
-Log-configuration file: {config_path:?}
-
-Current directory: {current_dir_str}
-Invocation path  : {inv_path:?}
-Path             : {path:?}
-").unwrap();
+  Ok(toml::Value::Table(table))
+}
 
-  // Arguments, if any
+/// Reads a log-configuration file, returning the configuration and its root filter directives.
+///
+/// This works like `tracing_config::config::read_config`, but supports all [`ConfigFormat`]s.
+fn read_config(file: &Path, format: ConfigFormat) -> Result<(TracingConfig, Option<String>), InitError> {
+  let text = fs::read_to_string(file)?;
+  let mut value = parse_config(&text, format).map_err(|source| InitError::Parse { format, source })?;
+  resolve_env_vars(&mut value)?;
+  let filter = filter_directives(&value);
+  let tracing_config = value.try_into().map_err(|err| InitError::Parse { format, source: Box::new(err) })?;
+  Ok((tracing_config, filter))
+}
 
-  let args: Vec<String> = std::env::args().skip(1).collect();
-  if !args.is_empty() {
-    ret.push_str("\nArguments:\n\n");
-    for arg in args {
-      writeln!(ret, "- {arg:?}").unwrap();
-    }
+/// Replaces references to environment variables of the form `${env:NAME}` in all strings of `value`.
+///
+/// `tracing_config::interpolate::toml::resolve_from_env_recursive` does the same, but for the `toml` version
+/// used by [`tracing_config`].
+fn resolve_env_vars(value: &mut toml::Value) -> Result<(), TracingConfigError> {
+  use tracing_config::config::RESOLVE_FROM_ENV_DEPTH;
+  use tracing_config::interpolate::resolve_from_env_recursive;
+
+  match value {
+    toml::Value::String(val) => *val = resolve_from_env_recursive(val, RESOLVE_FROM_ENV_DEPTH, "env")?,
+    toml::Value::Array(vals) => vals.iter_mut().try_for_each(resolve_env_vars)?,
+    toml::Value::Table(table) => table.iter_mut().try_for_each(|(_, val)| resolve_env_vars(val))?,
+    _ => {}
   }
+  Ok(())
+}
 
-  ret.pop(); // Strip trailing '\n'
-  ret.fence('#', config.text_width)
+/// Stores the filter directives returned by [`current_filter`].
+fn set_current_filter(filter: Option<String>) {
+  *CURRENT_FILTER.lock().unwrap_or_else(PoisonError::into_inner) = filter;
 }
 
 /// Initializes `tracing` for a binary executable with the given configuration.
@@ -241,7 +609,9 @@ Path             : {path:?}
 ///
 /// - [`InitError::Find`] if a [`FindError`] occurs
 /// - [`InitError::Io`] if an [`io::Error`] occurs
+/// - [`InitError::Parse`] if the log-configuration file cannot be parsed
 /// - [`InitError::TracingConfig`] if a [`TracingConfigError`] occurs
+/// - [`InitError::Validation`] if `config` is invalid, see [`Config::validate`]
 ///
 /// # Panics
 ///
@@ -277,16 +647,26 @@ Path             : {path:?}
 /// }
 #[allow(clippy::needless_doctest_main)]
 pub fn try_init(config: &Config) -> Result<ArcMutexGuard, InitError> {
-  assert!(config.exec_type == ExecType::Binary);
-  try_init_impl(config)
+  try_init_with_path(config).map(|(guard, _)| guard)
 }
 
-fn try_init_impl(config: &Config) -> Result<ArcMutexGuard, InitError> {
+fn try_init_impl(config: &Config) -> Result<(ArcMutexGuard, PathBuf), InitError> {
+  let _ = crate::process::start_instant(); // Capture the start instant as early as possible
+  config.validate()?;
+
+  // Apply environment
+
+  let config = &Config {
+    is_debug: config.is_debug || get_env_debug(config).unwrap_or(false),
+    paths: config.paths.clone().or_else(|| get_env(config)),
+    ..config.clone()
+  };
+
   // Look for configuration file
 
   let config_file = crate::config::find_config_file(
     config.exec_type,
-    "{}tracing.toml", // `file_name_pattern`
+    &config.file_name_pattern,
     config.is_debug,
     &config.name,
     config.paths.as_ref(),
@@ -295,7 +675,46 @@ fn try_init_impl(config: &Config) -> Result<ArcMutexGuard, InitError> {
 
   // Load configuration file
 
-  init_file(config, &config_file.1)
+  let guard = init_file(config, &config_file.1)?;
+  Ok((guard, config_file.1))
+}
+
+/// Initializes `tracing` for a binary executable with the given configuration, returning the path of the
+/// loaded log-configuration file as well.
+///
+/// This works like [`try_init`], but in addition to the guard, the result contains the path of the file
+/// that was loaded, e.g. to record it in the application's own diagnostics. Unlike [`Config::print_path`],
+/// this does not write to `stdout`.
+///
+/// # Errors
+///
+/// See [`try_init`].
+///
+/// # Panics
+///
+/// Panics if `config.exec_type` is not [`ExecType::Binary`].
+///
+/// # Examples
+///
+/// ```
+/// use meadows::process::ExecType;
+/// use meadows::tracing::config;
+///
+/// # #[cfg(not(miri))]
+/// # {
+/// let config = config::Config { print_path: false, ..config::Config::new(ExecType::Binary) };
+/// let _guard = match config::try_init_with_path(&config) {
+///   Ok((guard, path)) => {
+///     println!("Logging configured by {path:?}");
+///     Some(guard)
+///   }
+///   Err(_) => None,
+/// };
+/// # }
+/// ```
+pub fn try_init_with_path(config: &Config) -> Result<(ArcMutexGuard, PathBuf), InitError> {
+  assert!(config.exec_type == ExecType::Binary);
+  try_init_impl(config)
 }
 
 // Tests ====================================================================================================
@@ -311,8 +730,134 @@ mod tests {
 
   fn set_up() { init(&Config::new(ExecType::UnitTest)); }
 
+  // `Config` -----------------------------------------------------------------------------------------------
+
+  #[test]
+  fn test_config_validate() {
+    let config = Config::new(ExecType::UnitTest);
+    assert_eq!(config.env_var, None);
+    assert_eq!(config.file_name_pattern, "{}tracing.toml");
+    assert_eq!(config.validate(), Ok(()));
+
+    let config = Config { text_width: 0, ..Config::new(ExecType::UnitTest) };
+    assert_eq!(config.validate().unwrap_err().problems, vec![ConfigProblem::TextWidthTooSmall(0)]);
+
+    let config = Config { name: OsString::new(), ..Config::new(ExecType::UnitTest) };
+    assert_eq!(config.validate().unwrap_err().problems, vec![ConfigProblem::EmptyName]);
+
+    let config = Config { name: OsString::new(), text_width: 1, ..Config::new(ExecType::UnitTest) };
+    let err = config.validate().unwrap_err();
+    assert_eq!(err.problems, vec![ConfigProblem::EmptyName, ConfigProblem::TextWidthTooSmall(1)]);
+    assert_eq!(err.to_string(), "Invalid configuration: `name` is empty, `text_width` (1) is less than 2");
+
+    let file_name_pattern = String::from("tracing.toml");
+    let config = Config { file_name_pattern: file_name_pattern.clone(), ..Config::new(ExecType::UnitTest) };
+    let err = config.validate().unwrap_err();
+    assert_eq!(err.problems, vec![ConfigProblem::MissingPlaceholder(file_name_pattern)]);
+    assert_eq!(
+      err.to_string(),
+      "Invalid configuration: `file_name_pattern` (\"tracing.toml\") does not contain `{}`"
+    );
+  }
+
+  // `ConfigFormat` -----------------------------------------------------------------------------------------
+
+  #[test]
+  fn test_config_format() {
+    assert_eq!(ConfigFormat::default(), ConfigFormat::Toml);
+    assert_eq!(Config::new(ExecType::UnitTest).format, ConfigFormat::Toml);
+    assert_eq!(ConfigFormat::Json.to_string(), "JSON");
+    assert_eq!(ConfigFormat::Json.extension(), "json");
+    assert_eq!(ConfigFormat::Toml.to_string(), "TOML");
+    assert_eq!(ConfigFormat::Toml.extension(), "toml");
+    assert_eq!(ConfigFormat::Yaml.to_string(), "YAML");
+    assert_eq!(ConfigFormat::Yaml.extension(), "yaml");
+  }
+
+  // `StartInfo` --------------------------------------------------------------------------------------------
+
+  #[test]
+  fn test_start_info() {
+    let mut info = StartInfo {
+      args: vec![String::from("-v"), String::from("file name")],
+      config_path: Some(PathBuf::from("/etc/tracing.toml")),
+      current_dir: Some(PathBuf::from("/tmp")),
+      inv_name: String::from("app"),
+      inv_path: Some(PathBuf::from("./app")),
+      path: Some(PathBuf::from("/usr/bin/app")),
+      uptime: Some(Duration::from_millis(1500)),
+    };
+    assert_eq!(info.to_text(), "\
+Process started: app
+
+Log-configuration file: \"/etc/tracing.toml\"
+
+Current directory: \"/tmp\"
+Invocation path  : \"./app\"
+Path             : \"/usr/bin/app\"
+Uptime           : 1.500s
+
+Arguments:
+
+- \"-v\"
+- \"file name\"");
+    assert_eq!(info.to_fenced_text(40), info.to_text().fence('#', 40));
+
+    info.args.clear();
+    info.current_dir = None;
+    info.inv_path = None;
+    info.path = None;
+    info.uptime = None;
+    assert_eq!(info.to_text(), "Process started: app\n\nLog-configuration file: \"/etc/tracing.toml\"");
+
+    info.config_path = None;
+    assert_eq!(info.to_text(), "Process started: app");
+
+    let info = StartInfo::collect(Path::new("tracing.toml"));
+    assert_eq!(info.config_path.as_deref(), Some(Path::new("tracing.toml")));
+    assert_eq!(info.inv_name, crate::env::inv_name().to_string_lossy());
+    info.log_fields();
+  }
+
   // Functions ----------------------------------------------------------------------------------------------
 
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_current_filter() {
+    set_up();
+    // See `src/tracing.toml`
+    assert_eq!(current_filter().as_deref(), Some("trace,tracing_config::config=warn"));
+  }
+
+  #[test]
+  fn test_filter_directives() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let directives = |text| parse_config(text, ConfigFormat::Toml).map(|value| filter_directives(&value));
+
+    let text = "[filter.root]\nlevel = \"info\"\ndirectives = [\"a=warn\", \"b\"]\n";
+    assert_eq!(directives(text)?.as_deref(), Some("info,a=warn,b"));
+    assert_eq!(directives("[filter.root]\ndirectives = [\"a=warn\"]\n")?.as_deref(), Some("a=warn"));
+    assert_eq!(directives("title = \"no filter\"\n")?, None);
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_get_env() {
+    let config = Config {
+      debug_env_var: Some(String::from("MEADOWS_TEST_TRACING_CONFIG_DEBUG")),
+      env_var: Some(String::from("MEADOWS_TEST_TRACING_CONFIG")),
+      ..Config::new(ExecType::UnitTest)
+    };
+    assert_eq!(get_env(&config), None);
+    assert_eq!(get_env_debug(&config), None);
+
+    let _guard = crate::env::set_scoped("MEADOWS_TEST_TRACING_CONFIG", Some("a.toml"));
+    let _debug_guard = crate::env::set_scoped("MEADOWS_TEST_TRACING_CONFIG_DEBUG", Some("true"));
+    assert_eq!(get_env(&config), Some(OsString::from("a.toml")));
+    assert_eq!(get_env_debug(&config), Some(true));
+  }
+
   #[cfg_attr(miri, ignore)]
   #[test]
   fn test_init_1() {
@@ -332,6 +877,53 @@ mod tests {
       thread::sleep(Duration::from_millis(1));
     }
   }
+
+  #[test]
+  fn test_parse_config() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let text = "title = \"Test\"\n\n[filter.root]\nlevel = \"info\"\ndirectives = [\"a=warn\"]\n";
+    let expected = parse_config(text, ConfigFormat::Toml)?;
+
+    let text = r#"{"title": "Test", "filter": {"root": {"level": "info", "directives": ["a=warn"]}}}"#;
+    assert_eq!(parse_config(text, ConfigFormat::Json)?, expected);
+
+    let text = "title: Test\nfilter:\n  root:\n    level: info\n    directives:\n      - a=warn\n";
+    assert_eq!(parse_config(text, ConfigFormat::Yaml)?, expected);
+    assert!(parse_config(text, ConfigFormat::Toml).is_err()); // Not TOML
+
+    assert!(parse_config("title: ~\n", ConfigFormat::Yaml).is_err());
+    assert!(parse_config("- not a table\n", ConfigFormat::Yaml).is_err());
+
+    Ok(())
+  }
+
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn test_read_config() -> Result<(), InitError> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("tracing.yaml");
+
+    let _guard = crate::env::set_scoped("MEADOWS_TEST_FILTER", Some("debug"));
+    let text = "title: Test\nfilter:\n  root:\n    level: ${env:MEADOWS_TEST_FILTER}\n";
+    fs::write(&path, format!("{text}layer: {{}}\nwriter: {{}}\n"))?;
+    let (tracing_config, filter) = read_config(&path, ConfigFormat::Yaml)?;
+    assert_eq!(tracing_config.title, "Test");
+    assert_eq!(filter.as_deref(), Some("debug"));
+    assert_eq!(fs::read_dir(dir.path())?.count(), 1); // No temporary file is written
+
+    fs::write(&path, "[broken\n")?;
+    let err = read_config(&path, ConfigFormat::Yaml).unwrap_err();
+    assert_eq!(err.to_string(), "Cannot parse YAML configuration file");
+
+    // Valid YAML, but not a configuration
+    fs::write(&path, "title: Test\n")?;
+    assert!(matches!(read_config(&path, ConfigFormat::Yaml), Err(InitError::Parse { .. })));
+
+    // Unknown environment variable
+    fs::write(&path, "title: ${env:MEADOWS_TEST_UNKNOWN}\n")?;
+    assert!(matches!(read_config(&path, ConfigFormat::Yaml), Err(InitError::TracingConfig(_))));
+
+    Ok(())
+  }
 }
 
 // EOF
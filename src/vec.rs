@@ -11,6 +11,44 @@ use std::hash::Hash;
 ///
 /// This is included in the crate's [prelude](crate::prelude).
 pub trait VecExt<T> {
+  /// Removes *all* duplicates from a vector, retaining the order of the elements.
+  ///
+  /// The elements are cloned so they can be used as keys, see [`VecExt::dedup_all_by_key`].
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// let mut vec = vec![1, 2, 3, 2, 1];
+  /// vec.dedup_all();
+  /// assert_eq!(vec, vec![1, 2, 3])
+  /// ```
+  fn dedup_all(&mut self)
+  where
+    T: Clone + Eq + Hash;
+
+  /// Removes *all* duplicates from a vector based on an equality predicate, retaining the order of the
+  /// elements.
+  ///
+  /// `same` is passed an element and an element retained before it, in this order, and returns `true` if the
+  /// former is a duplicate of the latter. Because each element is compared to all elements retained so far,
+  /// this takes quadratic time and is meant for small vectors of elements that cannot produce a hashable
+  /// key. Otherwise, use [`VecExt::dedup_all_by_key`].
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// let mut vec = vec!["A", "a", "b", "B"];
+  /// vec.dedup_all_by(|a, b| a.eq_ignore_ascii_case(b));
+  /// assert_eq!(vec, vec!["A", "b"])
+  /// ```
+  fn dedup_all_by<F>(&mut self, same: F)
+  where
+    F: FnMut(&T, &T) -> bool;
+
   /// Removes *all* duplicates from a vector based on a key function, retaining the order of the elements.
   ///
   /// This is different from the vector's `dedup` methods, which only remove *consecutive* duplicates. The
@@ -30,9 +68,69 @@ pub trait VecExt<T> {
   where
     K: Eq + Hash,
     F: FnMut(&T) -> Option<K>;
+
+  /// Moves the element at index `from` to index `to`, shifting the elements in between by one position.
+  ///
+  /// After the call, the element is at index `to`. If `from` equals `to`, the vector is left unchanged.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `from` or `to` is out of bounds.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// let mut vec = vec!['a', 'b', 'c', 'd'];
+  /// vec.move_element(3, 1);
+  /// assert_eq!(vec, vec!['a', 'd', 'b', 'c'])
+  /// ```
+  fn move_element(&mut self, from: usize, to: usize);
+
+  /// Reorders a vector so that all elements satisfying `pred` come first, and returns the number of these
+  /// elements.
+  ///
+  /// The returned index splits the vector into the elements satisfying `pred` and the ones not satisfying
+  /// it. No second vector is allocated. As elements are swapped into place, the relative order of the
+  /// elements within each partition is not guaranteed to be preserved.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meadows::prelude::*;
+  ///
+  /// let mut vec = vec![1, 2, 3, 4, 5];
+  /// let idx = vec.partition_in_place(|&x| x % 2 == 0);
+  /// assert_eq!(idx, 2);
+  /// assert!(vec[..idx].iter().all(|&x| x % 2 == 0));
+  /// assert!(vec[idx..].iter().all(|&x| x % 2 != 0));
+  /// ```
+  fn partition_in_place<F>(&mut self, pred: F) -> usize
+  where
+    F: FnMut(&T) -> bool;
 }
 
 impl<T> VecExt<T> for Vec<T> {
+  fn dedup_all(&mut self)
+  where
+    T: Clone + Eq + Hash, {
+    self.dedup_all_by_key(|val| Some(val.clone()));
+  }
+
+  fn dedup_all_by<F>(&mut self, mut same: F)
+  where
+    F: FnMut(&T, &T) -> bool, {
+    let mut len = 0;
+    for i in 0..self.len() {
+      if !self[..len].iter().any(|kept| same(&self[i], kept)) {
+        self.swap(len, i);
+        len += 1;
+      }
+    }
+    self.truncate(len);
+  }
+
   fn dedup_all_by_key<K, F>(&mut self, mut f: F)
   where
     K: Eq + Hash,
@@ -40,6 +138,83 @@ impl<T> VecExt<T> for Vec<T> {
     let mut set = HashSet::new();
     self.retain(|val| if let Some(key) = f(val) { set.insert(key) } else { false });
   }
+
+  fn move_element(&mut self, from: usize, to: usize) {
+    let len = self.len();
+    assert!(from < len, "`from` (is {from}) should be < len (is {len})");
+    assert!(to < len, "`to` (is {to}) should be < len (is {len})");
+    if from < to {
+      self[from..=to].rotate_left(1);
+    } else {
+      self[to..=from].rotate_right(1);
+    }
+  }
+
+  fn partition_in_place<F>(&mut self, mut pred: F) -> usize
+  where
+    F: FnMut(&T) -> bool, {
+    let mut ret = 0;
+    for i in 0..self.len() {
+      if pred(&self[i]) {
+        self.swap(ret, i);
+        ret += 1;
+      }
+    }
+    ret
+  }
+}
+
+// Tests ====================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // `VecExt` -----------------------------------------------------------------------------------------------
+
+  #[test]
+  fn test_move_element() {
+    let mut vec = vec![0, 1, 2, 3, 4];
+    vec.move_element(1, 3);
+    assert_eq!(vec, [0, 2, 3, 1, 4]);
+    vec.move_element(0, 4);
+    assert_eq!(vec, [2, 3, 1, 4, 0]);
+
+    let mut vec = vec![0, 1, 2, 3, 4];
+    vec.move_element(3, 1);
+    assert_eq!(vec, [0, 3, 1, 2, 4]);
+    vec.move_element(4, 0);
+    assert_eq!(vec, [4, 0, 3, 1, 2]);
+
+    let mut vec = vec![0, 1, 2];
+    vec.move_element(1, 1);
+    assert_eq!(vec, [0, 1, 2]);
+  }
+
+  #[test]
+  #[should_panic(expected = "`from` (is 3) should be < len (is 3)")]
+  fn test_move_element_from_out_of_bounds() { vec![0, 1, 2].move_element(3, 0); }
+
+  #[test]
+  #[should_panic(expected = "`to` (is 3) should be < len (is 3)")]
+  fn test_move_element_to_out_of_bounds() { vec![0, 1, 2].move_element(0, 3); }
+
+  #[test]
+  fn test_partition_in_place() {
+    let mut vec = vec![5, 8, 1, 6, 2, 7, 4];
+    let idx = vec.partition_in_place(|&x| x < 5);
+    assert_eq!(idx, 3);
+    let (mut lo, mut hi) = (vec[..idx].to_vec(), vec[idx..].to_vec());
+    lo.sort_unstable();
+    hi.sort_unstable();
+    assert_eq!(lo, [1, 2, 4]);
+    assert_eq!(hi, [5, 6, 7, 8]);
+
+    let mut vec = vec![1, 2, 3];
+    assert_eq!(vec.partition_in_place(|_| true), 3);
+    assert_eq!(vec.partition_in_place(|_| false), 0);
+    assert_eq!(Vec::<i32>::new().partition_in_place(|_| true), 0);
+  }
 }
 
 // EOF